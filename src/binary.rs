@@ -0,0 +1,108 @@
+use crate::{Utf8Builder, Utf8Error, Utf8State};
+
+/// The heuristic of `Utf8Builder::set_binary_detection`, which tells binary data from text by inspecting the leading bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BinaryDetection {
+    /// The number of the leading bytes to inspect.
+    pub window:              usize,
+    /// The maximum percentage of the invalid bytes among all the bytes inspected so far. Data which contain more invalid bytes look binary.
+    pub max_invalid_percent: u8,
+}
+
+impl BinaryDetection {
+    /// Constructs a new `BinaryDetection`.
+    #[inline]
+    pub const fn new(window: usize, max_invalid_percent: u8) -> Self {
+        BinaryDetection {
+            window,
+            max_invalid_percent,
+        }
+    }
+}
+
+impl Default for BinaryDetection {
+    /// Inspects the first 8 KiB and allows 10% of invalid bytes.
+    #[inline]
+    fn default() -> Self {
+        BinaryDetection::new(8 * 1024, 10)
+    }
+}
+
+impl Utf8Builder {
+    /// Returns the heuristic which tells binary data from text.
+    #[inline]
+    pub fn binary_detection(&self) -> Option<BinaryDetection> {
+        self.binary_detection
+    }
+
+    /// Sets the heuristic which tells binary data from text. A push fails with `Utf8Error::LooksBinary` if the inspected bytes contain a NUL byte or too many invalid bytes, so that binary files can be skipped quickly.
+    #[inline]
+    pub fn set_binary_detection(&mut self, binary_detection: Option<BinaryDetection>) {
+        self.binary_detection = binary_detection;
+    }
+
+    /// Inspects the part of `data` within the window, and poisons the builder if it looks binary.
+    pub(crate) fn check_binary(&mut self, data: &[u8]) -> Result<(), Utf8Error> {
+        if self.binary_sample.inspect(self.binary_detection, self.state, data) {
+            return self.poison(Utf8Error::LooksBinary, data);
+        }
+
+        Ok(())
+    }
+}
+
+/// The running counts of the bytes inspected by the binary detection, so that the ratio does not depend on how the data are split into pushes.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct BinarySample {
+    inspected: usize,
+    invalid:   usize,
+}
+
+impl BinarySample {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        BinarySample {
+            inspected: 0, invalid: 0
+        }
+    }
+
+    /// Returns the number of the bytes inspected so far.
+    #[inline]
+    pub(crate) fn inspected(&self) -> usize {
+        self.inspected
+    }
+
+    /// Counts the part of `data` within the window, which follows the data validated into `state`. Returns whether the data look binary.
+    pub(crate) fn inspect(
+        &mut self,
+        detection: Option<BinaryDetection>,
+        mut state: Utf8State,
+        data: &[u8],
+    ) -> bool {
+        let detection = match detection {
+            Some(detection) if self.inspected < detection.window => detection,
+            _ => return false,
+        };
+
+        let inspected = &data[..data.len().min(detection.window - self.inspected)];
+
+        if inspected.contains(&0) {
+            return true;
+        }
+
+        for &b in inspected {
+            if state.push(b).is_err() {
+                self.invalid += 1;
+
+                // the byte breaking a character may begin another one
+                state = Utf8State::new();
+
+                let _ = state.push(b);
+            }
+        }
+
+        self.inspected += inspected.len();
+
+        self.invalid * 100 > detection.max_invalid_percent as usize * self.inspected
+    }
+}
@@ -0,0 +1,147 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{PushOutcome, Utf8Builder, Utf8Error};
+
+/// A `Utf8Builder` which maintains a sparse index of the byte offsets of every `interval` characters during pushes, so that a character can be located by scanning at most `interval` characters. Created by `Utf8Builder::with_char_index`.
+#[derive(Debug, Clone)]
+pub struct CharIndexedBuilder {
+    builder:     Utf8Builder,
+    interval:    usize,
+    /// the byte offsets of the characters at `interval`, `interval * 2`, ...
+    checkpoints: Vec<usize>,
+    /// the number of the indexed bytes, which are always on a character boundary
+    bytes:       usize,
+    /// the number of the indexed characters
+    chars:       usize,
+}
+
+impl Utf8Builder {
+    /// Returns the byte offset of the `n`-th complete character, or `None` if there are not so many complete characters. The characters before it are scanned, so use `with_char_index` for random access over huge data.
+    #[inline]
+    pub fn byte_offset_of_char(&self, n: usize) -> Option<usize> {
+        self.as_valid_str().char_indices().nth(n).map(|(i, _)| i)
+    }
+
+    /// Wraps this builder so that the byte offset of every `interval` characters is indexed, costing a `usize` per `interval` characters. An `interval` of zero is treated as one.
+    #[inline]
+    pub fn with_char_index(self, interval: usize) -> CharIndexedBuilder {
+        let mut indexed = CharIndexedBuilder {
+            builder:     self,
+            interval:    interval.max(1),
+            checkpoints: Vec::new(),
+            bytes:       0,
+            chars:       0,
+        };
+
+        indexed.index();
+
+        indexed
+    }
+}
+
+impl CharIndexedBuilder {
+    /// Gets a reference to the underlying builder.
+    #[inline]
+    pub fn builder(&self) -> &Utf8Builder {
+        &self.builder
+    }
+
+    /// Returns the number of characters between two indexed offsets.
+    #[inline]
+    pub fn interval(&self) -> usize {
+        self.interval
+    }
+
+    /// Returns the number of the complete characters.
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        self.chars
+    }
+
+    /// Pushes a byte.
+    #[inline]
+    pub fn push(&mut self, b: u8) -> Result<PushOutcome, Utf8Error> {
+        let result = self.builder.push(b);
+
+        self.index();
+
+        result
+    }
+
+    /// Pushes a `&str`.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), Utf8Error> {
+        let result = self.builder.push_str(s);
+
+        self.index();
+
+        result
+    }
+
+    /// Pushes a char.
+    #[inline]
+    pub fn push_char(&mut self, c: char) -> Result<(), Utf8Error> {
+        let result = self.builder.push_char(c);
+
+        self.index();
+
+        result
+    }
+
+    /// Pushes a chunk.
+    #[inline]
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<PushOutcome, Utf8Error> {
+        let result = self.builder.push_chunk(chunk);
+
+        self.index();
+
+        result
+    }
+
+    /// Returns the byte offset of the `n`-th complete character, or `None` if there are not so many complete characters. At most `interval` characters are scanned.
+    #[inline]
+    pub fn byte_offset_of_char(&self, n: usize) -> Option<usize> {
+        if n >= self.chars {
+            return None;
+        }
+
+        let start = match n / self.interval {
+            0 => 0,
+            i => self.checkpoints[i - 1],
+        };
+
+        let s = &self.builder.as_valid_str()[start..];
+
+        s.char_indices().nth(n % self.interval).map(|(i, _)| start + i)
+    }
+
+    /// Try to get the `String` instance.
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        self.builder.finalize()
+    }
+
+    /// Consumes this `CharIndexedBuilder`, returning the underlying builder.
+    #[inline]
+    pub fn into_builder(self) -> Utf8Builder {
+        self.builder
+    }
+
+    /// Indexes the complete characters which have not been indexed.
+    fn index(&mut self) {
+        let bytes = self.builder.as_valid_str().as_bytes();
+
+        for (i, &b) in bytes.iter().enumerate().skip(self.bytes) {
+            // not a continuation byte
+            if (b as i8) >= -0x40 {
+                if self.chars > 0 && self.chars % self.interval == 0 {
+                    self.checkpoints.push(i);
+                }
+
+                self.chars += 1;
+            }
+        }
+
+        self.bytes = bytes.len();
+    }
+}
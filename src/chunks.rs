@@ -0,0 +1,85 @@
+use alloc::{string::String, vec::Vec};
+use core::iter::FusedIterator;
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// An iterator over the complete characters of a `Utf8Builder` in pieces of at most `N` bytes, which never split a character. Created by `Utf8Builder::chunks`.
+#[derive(Debug, Clone)]
+pub struct StrChunks<'a> {
+    s:          &'a str,
+    chunk_size: usize,
+}
+
+impl Utf8Builder {
+    /// Creates an iterator over the complete characters in pieces of at most `chunk_size` bytes. A piece is shorter only if the next character does not fit, and a character longer than `chunk_size` is yielded alone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    pub fn chunks(&self, chunk_size: usize) -> StrChunks<'_> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+
+        StrChunks {
+            s: self.as_valid_str(),
+            chunk_size,
+        }
+    }
+
+    /// Try to get the data as segments of at most `segment_size` bytes which never split a character, like `chunks`, so that they can be compressed or sharded in parallel without looking for character boundaries again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segment_size` is 0.
+    #[inline]
+    pub fn finalize_segments(self, segment_size: usize) -> Result<Vec<String>, Utf8Error> {
+        assert!(segment_size != 0, "segment size must be non-zero");
+
+        self.check_finalize()?;
+
+        Ok(self.chunks(segment_size).map(String::from).collect())
+    }
+}
+
+impl<'a> Iterator for StrChunks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.s.is_empty() {
+            return None;
+        }
+
+        let mut end = self.chunk_size.min(self.s.len());
+
+        while !self.s.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        if end == 0 {
+            // the first character is longer than the chunk size
+            end = self.s.chars().next().unwrap().len_utf8();
+        }
+
+        let (chunk, rest) = self.s.split_at(end);
+
+        self.s = rest;
+
+        Some(chunk)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.s.len();
+
+        // a piece holds at most `chunk_size` bytes or a character alone, and only the last piece can be shorter than the room left by a character which does not fit
+        let widest = self.chunk_size.max(4);
+        let narrowest = self.chunk_size.saturating_sub(3).max(1);
+
+        (
+            len / widest + (len % widest != 0) as usize,
+            Some(len / narrowest + (len % narrowest != 0) as usize),
+        )
+    }
+}
+
+impl<'a> FusedIterator for StrChunks<'a> {}
@@ -0,0 +1,161 @@
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::fmt::{self, Display, Formatter};
+
+/// Which rules of UTF-8 are enforced by `check_conformance`. The relaxed rules let the corresponding forms pass, e.g. to accept WTF-8 or CESU-8 encoded surrogates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Strictness {
+    /// Rejects the overlong forms (`C0`, `C1`, `E0 80..9F` and `F0 80..8F`).
+    pub overlong:     bool,
+    /// Rejects the encoded surrogates (`ED A0..BF`).
+    pub surrogate:    bool,
+    /// Rejects the code points beyond U+10FFFF (`F4 90..BF` and `F5..F7`).
+    pub out_of_range: bool,
+}
+
+impl Strictness {
+    /// Enforces all rules except the one for surrogates, as WTF-8 does.
+    pub const ALLOW_SURROGATES: Strictness =
+        Strictness {
+            overlong: true, surrogate: false, out_of_range: true
+        };
+    /// Enforces all rules, as `Utf8Builder` does.
+    pub const STRICT: Strictness =
+        Strictness {
+            overlong: true, surrogate: true, out_of_range: true
+        };
+}
+
+impl Default for Strictness {
+    #[inline]
+    fn default() -> Self {
+        Strictness::STRICT
+    }
+}
+
+/// The rule of UTF-8 which is violated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViolationKind {
+    /// A byte can never appear there (e.g. a stray continuation byte, a missing continuation byte, or `F8..FF`).
+    UnexpectedByte,
+    /// A character is encoded in more bytes than needed.
+    Overlong,
+    /// A surrogate code point (U+D800 to U+DFFF) is encoded.
+    Surrogate,
+    /// A code point beyond U+10FFFF is encoded.
+    OutOfRange,
+    /// The data end in the middle of a character.
+    Truncated,
+}
+
+/// A violation found by `check_conformance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Violation {
+    /// The offset of the character which violates the rule.
+    pub offset: usize,
+    /// The violated rule.
+    pub kind:   ViolationKind,
+}
+
+#[cfg(feature = "std")]
+impl Display for Violation {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let rule = match self.kind {
+            ViolationKind::UnexpectedByte => "unexpected byte",
+            ViolationKind::Overlong => "overlong form",
+            ViolationKind::Surrogate => "encoded surrogate",
+            ViolationKind::OutOfRange => "code point beyond U+10FFFF",
+            ViolationKind::Truncated => "truncated character",
+        };
+
+        write!(f, "{} at offset {}", rule, self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Violation {}
+
+/// Checks the data against the rules of UTF-8 enforced by `strictness`, and reports the first violation with the rule it violates, for conformance test suites which need to tell the rules apart.
+///
+/// ```rust
+/// use utf8_builder::{Strictness, ViolationKind};
+///
+/// let err =
+///     utf8_builder::check_conformance(b"a\xED\xA0\x80", Strictness::STRICT)
+///         .unwrap_err();
+///
+/// assert_eq!(1, err.offset);
+/// assert_eq!(ViolationKind::Surrogate, err.kind);
+///
+/// assert!(utf8_builder::check_conformance(
+///     b"a\xED\xA0\x80",
+///     Strictness::ALLOW_SURROGATES
+/// )
+/// .is_ok());
+/// ```
+pub fn check_conformance(data: &[u8], strictness: Strictness) -> Result<(), Violation> {
+    let mut i = 0;
+
+    while i < data.len() {
+        let lead = data[i];
+
+        let violation = |kind| Violation {
+            offset: i,
+            kind,
+        };
+
+        let (width, rule) = match lead {
+            0x00..=0x7F => {
+                i += 1;
+
+                continue;
+            },
+            0xC0 | 0xC1 => (2, Some(ViolationKind::Overlong)),
+            0xC2..=0xDF => (2, None),
+            0xE0..=0xEF => (3, None),
+            0xF0..=0xF4 => (4, None),
+            0xF5..=0xF7 => (4, Some(ViolationKind::OutOfRange)),
+            _ => return Err(violation(ViolationKind::UnexpectedByte)),
+        };
+
+        let end = i + width;
+
+        let mut j = i + 1;
+
+        while j < end && j < data.len() {
+            if data[j] & 0xC0 != 0x80 {
+                return Err(violation(ViolationKind::UnexpectedByte));
+            }
+
+            j += 1;
+        }
+
+        if j < end {
+            return Err(violation(ViolationKind::Truncated));
+        }
+
+        let rule = rule.or(match (lead, data[i + 1]) {
+            (0xE0, 0x80..=0x9F) | (0xF0, 0x80..=0x8F) => Some(ViolationKind::Overlong),
+            (0xED, 0xA0..=0xBF) => Some(ViolationKind::Surrogate),
+            (0xF4, 0x90..=0xBF) => Some(ViolationKind::OutOfRange),
+            _ => None,
+        });
+
+        let enforced = match rule {
+            Some(ViolationKind::Overlong) => strictness.overlong,
+            Some(ViolationKind::Surrogate) => strictness.surrogate,
+            Some(ViolationKind::OutOfRange) => strictness.out_of_range,
+            _ => false,
+        };
+
+        if enforced {
+            return Err(violation(rule.unwrap()));
+        }
+
+        i = end;
+    }
+
+    Ok(())
+}
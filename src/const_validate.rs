@@ -0,0 +1,60 @@
+use crate::state::second_byte_range;
+
+/// Validates a fixed-size byte array at compile time, with the same rules as `Utf8Builder`.
+///
+/// ```rust
+/// const VALID: bool = utf8_builder::validate_const(*b"\xE4\xB8\xAD");
+///
+/// assert!(VALID);
+/// ```
+#[inline]
+pub const fn validate_const<const N: usize>(bytes: [u8; N]) -> bool {
+    validate_slice_const(&bytes)
+}
+
+/// Validates a byte slice at compile time, with the same rules as `Utf8Builder`.
+pub const fn validate_slice_const(bytes: &[u8]) -> bool {
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let w = utf8_width::get_width(bytes[i]);
+
+        if w == 0 || i + w > bytes.len() {
+            return false;
+        }
+
+        let mut j = 1;
+
+        while j < w {
+            let (min, max) = if j == 1 { second_byte_range(bytes[i]) } else { (0x80, 0xBF) };
+
+            let b = bytes[i + j];
+
+            if b < min || b > max {
+                return false;
+            }
+
+            j += 1;
+        }
+
+        i += w;
+    }
+
+    true
+}
+
+/// Converts a static byte slice into a `&'static str` at compile time, or returns `None` if it is not valid UTF-8.
+///
+/// ```rust
+/// const TEXT: Option<&str> = utf8_builder::str_from_static(b"\xE4\xB8\xAD");
+///
+/// assert_eq!(Some("中"), TEXT);
+/// ```
+#[inline]
+pub const fn str_from_static(bytes: &'static [u8]) -> Option<&'static str> {
+    if validate_slice_const(bytes) {
+        Some(unsafe { core::str::from_utf8_unchecked(bytes) })
+    } else {
+        None
+    }
+}
@@ -0,0 +1,36 @@
+use alloc::string::String;
+
+use crate::{PushOutcome, Utf8Builder, Utf8Error};
+
+// the variants of the pushes and `finalize` which convert the error into another type (e.g. the error enum of a parser). `?` already converts the error in the middle of a function, so these are for the places which `?` does not reach: the tail expression of a function, and the closures of combinators such as `and_then` or `try_for_each`, where the error type has to match without a `map_err`.
+impl Utf8Builder {
+    /// Pushes a byte, like `push`.
+    #[inline]
+    pub fn push_with<E: From<Utf8Error>>(&mut self, b: u8) -> Result<PushOutcome, E> {
+        self.push(b).map_err(E::from)
+    }
+
+    /// Pushes a `&str`, like `push_str`.
+    #[inline]
+    pub fn push_str_with<E: From<Utf8Error>>(&mut self, s: &str) -> Result<(), E> {
+        self.push_str(s).map_err(E::from)
+    }
+
+    /// Pushes a char, like `push_char`.
+    #[inline]
+    pub fn push_char_with<E: From<Utf8Error>>(&mut self, c: char) -> Result<(), E> {
+        self.push_char(c).map_err(E::from)
+    }
+
+    /// Pushes a chunk, like `push_chunk`.
+    #[inline]
+    pub fn push_chunk_with<E: From<Utf8Error>>(&mut self, chunk: &[u8]) -> Result<PushOutcome, E> {
+        self.push_chunk(chunk).map_err(E::from)
+    }
+
+    /// Try to get the `String` instance, like `finalize`.
+    #[inline]
+    pub fn finalize_with<E: From<Utf8Error>>(self) -> Result<String, E> {
+        self.finalize().map_err(E::from)
+    }
+}
@@ -0,0 +1,100 @@
+use alloc::{borrow::Cow, string::String};
+
+use crate::{PushOutcome, Utf8Builder, Utf8Error};
+
+#[derive(Debug, Clone)]
+enum Storage<'a> {
+    Borrowed(&'a str),
+    Owned(Utf8Builder),
+}
+
+/// A builder seeded from a `&str`, which keeps borrowing it until a push actually appends data. Only then is the data copied into a `Utf8Builder`.
+#[derive(Debug, Clone)]
+pub struct CowBuilder<'a> {
+    storage: Storage<'a>,
+}
+
+impl<'a> CowBuilder<'a> {
+    /// Constructs a new `CowBuilder` borrowing `s`.
+    #[inline]
+    pub const fn new(s: &'a str) -> Self {
+        CowBuilder {
+            storage: Storage::Borrowed(s)
+        }
+    }
+
+    /// Returns `true` if the data are still borrowed.
+    #[inline]
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.storage, Storage::Borrowed(_))
+    }
+
+    /// Extracts a string slice containing all complete characters. The incomplete character (if any) is excluded.
+    #[inline]
+    pub fn as_valid_str(&self) -> &str {
+        match &self.storage {
+            Storage::Borrowed(s) => s,
+            Storage::Owned(builder) => builder.as_valid_str(),
+        }
+    }
+
+    /// Pushes a `&str`.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), Utf8Error> {
+        if s.is_empty() {
+            return Ok(());
+        }
+
+        self.to_mut(s.len()).push_str(s)
+    }
+
+    /// Pushes a char.
+    #[inline]
+    pub fn push_char(&mut self, c: char) -> Result<(), Utf8Error> {
+        self.to_mut(c.len_utf8()).push_char(c)
+    }
+
+    /// Pushes a chunk. Returns whether the data end on a character boundary afterwards.
+    #[inline]
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<PushOutcome, Utf8Error> {
+        if chunk.is_empty() {
+            if let Storage::Borrowed(_) = self.storage {
+                return Ok(PushOutcome::Complete);
+            }
+        }
+
+        self.to_mut(chunk.len()).push_chunk(chunk)
+    }
+
+    /// Try to get the data, which are still borrowed if nothing has been appended.
+    #[inline]
+    pub fn finalize(self) -> Result<Cow<'a, str>, Utf8Error> {
+        match self.storage {
+            Storage::Borrowed(s) => Ok(Cow::Borrowed(s)),
+            Storage::Owned(builder) => builder.finalize().map(Cow::Owned),
+        }
+    }
+
+    /// Gets the builder, copying the borrowed data into it with room for `additional` more bytes if needed.
+    fn to_mut(&mut self, additional: usize) -> &mut Utf8Builder {
+        if let Storage::Borrowed(s) = self.storage {
+            let mut buffer = String::with_capacity(s.len().saturating_add(additional));
+
+            buffer.push_str(s);
+
+            self.storage = Storage::Owned(Utf8Builder::from(buffer));
+        }
+
+        match &mut self.storage {
+            Storage::Owned(builder) => builder,
+            Storage::Borrowed(_) => unreachable!(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for CowBuilder<'a> {
+    #[inline]
+    fn from(s: &'a str) -> Self {
+        CowBuilder::new(s)
+    }
+}
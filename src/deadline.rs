@@ -0,0 +1,145 @@
+use alloc::string::String;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+#[cfg(feature = "std")]
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use futures_core::{ready, Stream};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// Why `AccumulateWithDeadline` has failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccumulateFailure {
+    /// The deadline has passed before the stream ended.
+    DeadlineExceeded,
+    /// A push has failed, or the data end with an incomplete character.
+    Utf8(Utf8Error),
+}
+
+/// The error of `AccumulateWithDeadline`, with the data accumulated so far.
+#[derive(Debug, Clone)]
+pub struct AccumulateError {
+    /// Why the accumulation has failed.
+    pub failure: AccumulateFailure,
+    /// The number of the received chunks.
+    pub chunks:  u64,
+    /// The number of the received bytes, including the rejected ones.
+    pub bytes:   u64,
+    /// The builder holding the data accumulated so far.
+    pub builder: Utf8Builder,
+}
+
+#[cfg(feature = "std")]
+impl Display for AccumulateError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.failure {
+            AccumulateFailure::DeadlineExceeded => f.write_str("the deadline has passed")?,
+            AccumulateFailure::Utf8(err) => Display::fmt(&err, f)?,
+        }
+
+        write!(f, " after {} bytes in {} chunks", self.bytes, self.chunks)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for AccumulateError {}
+
+/// A future which drives a stream of byte chunks into a `Utf8Builder` until the stream ends or the deadline passes. Created by `Utf8Builder::accumulate_with_deadline`.
+#[derive(Debug)]
+pub struct AccumulateWithDeadline<S, D> {
+    stream:   S,
+    deadline: D,
+    builder:  Option<Utf8Builder>,
+    chunks:   u64,
+    bytes:    u64,
+}
+
+impl Utf8Builder {
+    /// Pushes every chunk of `stream` into this builder and resolves to the `String` instance when the stream ends, or to an `AccumulateError` with the partial data as soon as `deadline` (e.g. a timer of any runtime) completes or a push fails. `deadline` is polled before every chunk, so a stream which is always ready cannot outrun it. The size limit of this builder is applied as usual.
+    #[inline]
+    pub fn accumulate_with_deadline<S: Stream, D: Future>(
+        self,
+        stream: S,
+        deadline: D,
+    ) -> AccumulateWithDeadline<S, D>
+    where
+        S::Item: AsRef<[u8]>, {
+        AccumulateWithDeadline {
+            stream,
+            deadline,
+            builder: Some(self),
+            chunks: 0,
+            bytes: 0,
+        }
+    }
+}
+
+impl<S: Stream, D: Future> Future for AccumulateWithDeadline<S, D>
+where
+    S::Item: AsRef<[u8]>,
+{
+    type Output = Result<String, AccumulateError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // `stream` and `deadline` are the only structurally pinned fields and they are never moved out
+        let this = unsafe { self.get_unchecked_mut() };
+
+        loop {
+            // the deadline is checked before every chunk, since a stream which is always ready would never yield otherwise
+            let deadline = unsafe { Pin::new_unchecked(&mut this.deadline) };
+
+            if deadline.poll(cx).is_ready() {
+                return Poll::Ready(Err(this.fail(AccumulateFailure::DeadlineExceeded)));
+            }
+
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+            let builder = this.builder.as_mut().expect("polled after completion");
+
+            match ready!(stream.poll_next(cx)) {
+                Some(chunk) => {
+                    let chunk = chunk.as_ref();
+
+                    this.chunks += 1;
+                    this.bytes += chunk.len() as u64;
+
+                    if let Err(err) = builder.push_chunk(chunk) {
+                        return Poll::Ready(Err(this.fail(AccumulateFailure::Utf8(err))));
+                    }
+                },
+                None => {
+                    let builder = this.builder.take().unwrap();
+
+                    return Poll::Ready(match builder.check_finalize() {
+                        Ok(()) => Ok(builder.finalize().unwrap()),
+                        Err(err) => {
+                            this.builder = Some(builder);
+
+                            Err(this.fail(AccumulateFailure::Utf8(err)))
+                        },
+                    });
+                },
+            }
+        }
+    }
+}
+
+impl<S, D> AccumulateWithDeadline<S, D> {
+    #[inline]
+    fn fail(&mut self, failure: AccumulateFailure) -> AccumulateError {
+        AccumulateError {
+            failure,
+            chunks: self.chunks,
+            bytes: self.bytes,
+            builder: self.builder.take().expect("polled after completion"),
+        }
+    }
+}
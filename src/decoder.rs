@@ -0,0 +1,204 @@
+use alloc::string::String;
+
+use crate::Utf8State;
+
+/// The result of `Utf8Decoder::decode_to_string`, shaped like `encoding_rs::CoderResult`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoderResult {
+    /// All the input has been consumed.
+    InputEmpty,
+    /// The spare capacity of the output is not enough for the next character.
+    OutputFull,
+}
+
+/// The result of `Utf8Decoder::decode_to_string_without_replacement`, shaped like `encoding_rs::DecoderResult`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecoderResult {
+    /// All the input has been consumed.
+    InputEmpty,
+    /// The spare capacity of the output is not enough for the next character.
+    OutputFull,
+    /// A malformed sequence is found. The first value is the length of the malformed sequence (which may have been read in the previous calls), and the second value is the number of bytes read after it.
+    Malformed(u8, u8),
+}
+
+/// A UTF-8 decoder with an interface shaped like `encoding_rs::Decoder`, backed by the validator of this crate.
+///
+/// Like `encoding_rs`, the output is written only into the spare capacity of `dst`, and it is never reallocated.
+#[derive(Debug, Clone, Default)]
+pub struct Utf8Decoder {
+    state:               Utf8State,
+    /// a malformed sequence has been read, but its U+FFFD has not been written because the output was full
+    pending_replacement: bool,
+}
+
+impl Utf8Decoder {
+    /// Constructs a new `Utf8Decoder`.
+    #[inline]
+    pub const fn new() -> Self {
+        Utf8Decoder {
+            state: Utf8State::new(), pending_replacement: false
+        }
+    }
+
+    /// Returns the maximum number of output bytes for decoding `byte_length` more bytes with replacement.
+    #[inline]
+    pub fn max_utf8_buffer_length(&self, byte_length: usize) -> Option<usize> {
+        // every byte can be turned into a U+FFFD in the worst case
+        byte_length.checked_add(self.state.pending().len())?.checked_mul(3)
+    }
+
+    /// Returns the maximum number of output bytes for decoding `byte_length` more bytes without replacement.
+    #[inline]
+    pub fn max_utf8_buffer_length_without_replacement(&self, byte_length: usize) -> Option<usize> {
+        byte_length.checked_add(self.state.pending().len())
+    }
+
+    /// Decodes `src` into the spare capacity of `dst`, replacing malformed sequences with U+FFFD. Set `last` to `true` for the last call. Returns the result, the number of bytes read, and whether any replacement happened.
+    pub fn decode_to_string(
+        &mut self,
+        src: &[u8],
+        dst: &mut String,
+        last: bool,
+    ) -> (CoderResult, usize, bool) {
+        let mut read = 0;
+        let mut had_replacements = false;
+
+        loop {
+            if self.pending_replacement {
+                if spare(dst) < '\u{FFFD}'.len_utf8() {
+                    return (CoderResult::OutputFull, read, had_replacements);
+                }
+
+                dst.push('\u{FFFD}');
+
+                self.pending_replacement = false;
+                had_replacements = true;
+            }
+
+            match self.decode(&src[read..], dst, last) {
+                (DecoderResult::InputEmpty, n) => {
+                    return (CoderResult::InputEmpty, read + n, had_replacements)
+                },
+                (DecoderResult::OutputFull, n) => {
+                    return (CoderResult::OutputFull, read + n, had_replacements)
+                },
+                (DecoderResult::Malformed(..), n) => {
+                    // the malformed sequence has been consumed, so its replacement is kept until the output has room for it
+                    read += n;
+
+                    self.pending_replacement = true;
+                },
+            }
+        }
+    }
+
+    /// Decodes `src` into the spare capacity of `dst`, stopping at the first malformed sequence. Set `last` to `true` for the last call. Returns the result and the number of bytes read.
+    #[inline]
+    pub fn decode_to_string_without_replacement(
+        &mut self,
+        src: &[u8],
+        dst: &mut String,
+        last: bool,
+    ) -> (DecoderResult, usize) {
+        self.decode(src, dst, last)
+    }
+
+    fn decode(&mut self, src: &[u8], dst: &mut String, last: bool) -> (DecoderResult, usize) {
+        let mut read = 0;
+
+        loop {
+            // complete the incomplete character first
+            while !self.state.is_valid() && read < src.len() {
+                let mut state = self.state;
+
+                if state.push(src[read]).is_err() {
+                    // the incomplete character is malformed and the current byte is not consumed
+                    let len = self.state.pending().len() as u8;
+
+                    self.state = Utf8State::new();
+
+                    return (DecoderResult::Malformed(len, 0), read);
+                }
+
+                if state.is_valid() {
+                    let pending = self.state.pending();
+                    let width = pending.len() + 1;
+
+                    if spare(dst) < width {
+                        return (DecoderResult::OutputFull, read);
+                    }
+
+                    let mut bytes = [0; 4];
+
+                    bytes[..pending.len()].copy_from_slice(pending);
+                    bytes[pending.len()] = src[read];
+
+                    dst.push_str(unsafe { core::str::from_utf8_unchecked(&bytes[..width]) });
+                }
+
+                self.state = state;
+                read += 1;
+            }
+
+            if read == src.len() {
+                break;
+            }
+
+            let rest = &src[read..];
+            let available = spare(dst);
+
+            if available == 0 {
+                return (DecoderResult::OutputFull, read);
+            }
+
+            let limited = &rest[..rest.len().min(available)];
+
+            match core::str::from_utf8(limited) {
+                Ok(s) => {
+                    dst.push_str(s);
+
+                    read += limited.len();
+                },
+                Err(err) => {
+                    let valid_up_to = err.valid_up_to();
+
+                    dst.push_str(unsafe {
+                        core::str::from_utf8_unchecked(&limited[..valid_up_to])
+                    });
+
+                    read += valid_up_to;
+
+                    match err.error_len() {
+                        Some(len) => {
+                            return (DecoderResult::Malformed(len as u8, 0), read + len);
+                        },
+                        None => {
+                            // keep the incomplete character in the state
+                            for b in limited[valid_up_to..].iter().copied() {
+                                self.state.push(b).unwrap();
+                            }
+
+                            read += limited.len() - valid_up_to;
+                        },
+                    }
+                },
+            }
+        }
+
+        if last && !self.state.is_valid() {
+            let len = self.state.pending().len() as u8;
+
+            self.state = Utf8State::new();
+
+            return (DecoderResult::Malformed(len, 0), read);
+        }
+
+        (DecoderResult::InputEmpty, read)
+    }
+}
+
+#[inline]
+fn spare(dst: &String) -> usize {
+    dst.capacity() - dst.len()
+}
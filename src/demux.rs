@@ -0,0 +1,87 @@
+use alloc::{collections::BTreeMap, string::String};
+
+use crate::{PushOutcome, Utf8Builder, Utf8Error};
+
+/// A demultiplexer which owns a builder for every key (e.g. the ID of a stream of a multiplexed connection) and routes chunks to them, with a byte budget shared by all of them.
+#[derive(Debug, Clone)]
+pub struct Utf8Demux<K> {
+    builders: BTreeMap<K, Utf8Builder>,
+    budget:   usize,
+    /// the number of bytes held by all the builders
+    used:     usize,
+}
+
+impl<K: Ord> Utf8Demux<K> {
+    /// Constructs a new, empty `Utf8Demux` whose builders can hold at most `budget` bytes in total.
+    #[inline]
+    pub fn new(budget: usize) -> Self {
+        Utf8Demux {
+            builders: BTreeMap::new(),
+            budget,
+            used: 0,
+        }
+    }
+
+    /// Returns the maximum number of bytes held by all the builders.
+    #[inline]
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Returns the number of bytes held by all the builders.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.used
+    }
+
+    /// Returns the number of the builders.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.builders.len()
+    }
+
+    /// Returns `true` if there are no builders.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.builders.is_empty()
+    }
+
+    /// Gets a reference to the builder of a key.
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<&Utf8Builder> {
+        self.builders.get(key)
+    }
+
+    /// Pushes a chunk into the builder of a key, which is created if it does not exist. A push which would exceed the budget fails with `Utf8Error::LimitExceeded` without pushing anything. If the chunk is invalid, only the builder of the key is poisoned.
+    pub fn push_chunk(&mut self, key: K, chunk: &[u8]) -> Result<PushOutcome, Utf8Error> {
+        if chunk.len() > self.budget - self.used {
+            return Err(Utf8Error::LimitExceeded);
+        }
+
+        let builder = self.builders.entry(key).or_default();
+
+        let len = builder.len();
+
+        let result = builder.push_chunk(chunk);
+
+        self.used += builder.len() - len;
+
+        result
+    }
+
+    /// Removes the builder of a key and tries to get its `String` instance. The bytes held by the builder are returned to the budget.
+    #[inline]
+    pub fn finalize(&mut self, key: &K) -> Option<Result<String, Utf8Error>> {
+        self.remove(key).map(Utf8Builder::finalize)
+    }
+
+    /// Removes the builder of a key. The bytes held by the builder are returned to the budget.
+    #[inline]
+    pub fn remove(&mut self, key: &K) -> Option<Utf8Builder> {
+        let builder = self.builders.remove(key)?;
+
+        self.used -= builder.len();
+
+        Some(builder)
+    }
+}
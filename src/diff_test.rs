@@ -0,0 +1,28 @@
+use crate::Utf8Builder;
+
+impl Utf8Builder {
+    /// Cross-checks the data against `core::str::from_utf8`. Panics with a dump of the data if the results differ.
+    pub(crate) fn cross_check(&self) {
+        let bytes = self.bytes();
+        let valid_len = self.valid_len();
+        let pending = self.state.pending();
+
+        let agreed = match core::str::from_utf8(bytes) {
+            Ok(_) => pending.is_empty(),
+            // the data may only end with the incomplete character recorded by the state
+            Err(err) => {
+                err.error_len().is_none()
+                    && err.valid_up_to() == valid_len
+                    && &bytes[valid_len..] == pending
+            },
+        };
+
+        if !agreed {
+            panic!(
+                "the validation disagrees with `core::str::from_utf8`: valid_len = {}, pending = \
+                 {:02X?}, data = {:02X?}",
+                valid_len, pending, bytes
+            );
+        }
+    }
+}
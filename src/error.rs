@@ -1,18 +1,112 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
 #[cfg(feature = "std")]
 use std::error::Error;
 #[cfg(feature = "std")]
 use std::fmt::{self, Display, Formatter};
 
-#[derive(Debug)]
-pub struct Utf8Error;
+/// Errors which can occur when building UTF-8 data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Utf8Error {
+    /// The data are not valid UTF-8.
+    Invalid,
+    /// The data end in the middle of a character, which has `have` bytes and needs `need` more bytes. More data may make them valid.
+    Incomplete { have: u8, need: u8 },
+    /// The data would exceed the size limit of the builder.
+    LimitExceeded,
+    /// A policy (e.g. a `PushObserver`) has rejected the data.
+    PolicyViolated,
+    /// The data look binary, judged by `Utf8Builder::set_binary_detection`.
+    LooksBinary,
+    /// The memory cannot be allocated. It is returned instead of panicking only if the `no-panic` feature is enabled.
+    AllocationFailed,
+}
 
 #[cfg(feature = "std")]
 impl Display for Utf8Error {
     #[inline]
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        f.write_str("incorrect UTF-8 data")
+        match self {
+            Utf8Error::Invalid => f.write_str("incorrect UTF-8 data"),
+            Utf8Error::Incomplete {
+                have,
+                need,
+            } => write!(f, "incomplete UTF-8 data ({} more bytes needed after {})", need, have),
+            Utf8Error::LimitExceeded => f.write_str("the size limit is exceeded"),
+            Utf8Error::PolicyViolated => f.write_str("the policy is violated"),
+            Utf8Error::LooksBinary => f.write_str("the data look binary"),
+            Utf8Error::AllocationFailed => f.write_str("the memory allocation failed"),
+        }
     }
 }
 
 #[cfg(feature = "std")]
 impl Error for Utf8Error {}
+
+/// The error of a failed push, which poisons a `Utf8Builder`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushError {
+    /// The kind of the error.
+    pub kind:    Utf8Error,
+    /// The number of bytes of the complete characters in the builder when the push failed, which is where the rejected data begin.
+    pub offset:  usize,
+    /// The bytes around the offending position, if `Utf8Builder::set_error_context` is used.
+    pub context: Option<ErrorContext>,
+}
+
+#[cfg(feature = "std")]
+impl Display for PushError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.offset)?;
+
+        if let Some(context) = self.context.as_ref() {
+            write!(f, " (near {})", context.to_hex())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The bytes captured around the offending position of a failed push.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The bytes before the offending position.
+    pub before: Vec<u8>,
+    /// The bytes from the offending position on.
+    pub after:  Vec<u8>,
+}
+
+impl ErrorContext {
+    /// Renders the bytes in hexadecimal, with `|` at the offending position, like `61 62 | FF 63`.
+    pub fn to_hex(&self) -> String {
+        let mut s = String::with_capacity((self.before.len() + self.after.len()) * 3 + 2);
+
+        for b in self.before.iter() {
+            write!(s, "{:02X} ", b).unwrap();
+        }
+
+        s.push('|');
+
+        for b in self.after.iter() {
+            write!(s, " {:02X}", b).unwrap();
+        }
+
+        s
+    }
+
+    /// Renders the bytes lossily, with `|` at the offending position, like `ab|\u{FFFD}c`.
+    #[inline]
+    pub fn to_lossy(&self) -> String {
+        let mut s = String::from_utf8_lossy(&self.before).into_owned();
+
+        s.push('|');
+        s.push_str(&String::from_utf8_lossy(&self.after));
+
+        s
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PushError {}
@@ -0,0 +1,98 @@
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::fmt::{self, Display, Formatter};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// Extends a collection with the contents of an iterator, stopping at the first item which cannot be accepted.
+pub trait TryExtend<A> {
+    /// The error type of the collection, which should tell how many items have been accepted.
+    type Error;
+
+    /// Extends the collection with the items of `iter`, and returns the number of the accepted items. Nothing after the first rejected item is taken from the iterator, so the rest can be resumed with `iter.by_ref()`.
+    fn try_extend<I: IntoIterator<Item = A>>(&mut self, iter: I) -> Result<usize, Self::Error>;
+}
+
+/// The error of `TryExtend` for `Utf8Builder`, with the number of the items accepted before the rejected one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtendError {
+    /// The number of the accepted items. One more item has been taken from the iterator and rejected.
+    pub accepted: usize,
+    /// Why the item is rejected.
+    pub kind:     Utf8Error,
+}
+
+#[cfg(feature = "std")]
+impl Display for ExtendError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} after {} items", self.kind, self.accepted)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ExtendError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.kind)
+    }
+}
+
+impl From<ExtendError> for Utf8Error {
+    #[inline]
+    fn from(error: ExtendError) -> Self {
+        error.kind
+    }
+}
+
+impl TryExtend<u8> for Utf8Builder {
+    type Error = ExtendError;
+
+    #[inline]
+    fn try_extend<I: IntoIterator<Item = u8>>(&mut self, iter: I) -> Result<usize, Self::Error> {
+        let mut accepted = 0;
+
+        for b in iter {
+            self.push(b).map_err(|kind| ExtendError {
+                accepted,
+                kind,
+            })?;
+
+            accepted += 1;
+        }
+
+        Ok(accepted)
+    }
+}
+
+impl TryExtend<char> for Utf8Builder {
+    type Error = ExtendError;
+
+    #[inline]
+    fn try_extend<I: IntoIterator<Item = char>>(&mut self, iter: I) -> Result<usize, Self::Error> {
+        let mut accepted = 0;
+
+        for c in iter {
+            self.push_char(c).map_err(|kind| ExtendError {
+                accepted,
+                kind,
+            })?;
+
+            accepted += 1;
+        }
+
+        Ok(accepted)
+    }
+}
+
+impl Utf8Builder {
+    /// Pushes the bytes of an iterator until the first rejected byte, and returns the number of the pushed bytes. If a byte is rejected, the iterator is not advanced beyond it, and the error tells how many bytes have been pushed before it. An invalid byte poisons the builder, while exceeding the limit does not, so a smaller push can still follow.
+    #[inline]
+    pub fn try_extend_from_iter<I: IntoIterator<Item = u8>>(
+        &mut self,
+        iter: I,
+    ) -> Result<usize, ExtendError> {
+        self.try_extend(iter)
+    }
+}
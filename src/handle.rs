@@ -0,0 +1,169 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// The result codes of `Utf8Handles`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(i32)]
+pub enum ResultCode {
+    /// The operation succeeded.
+    Ok               = 0,
+    /// The data are not valid UTF-8.
+    Invalid          = 1,
+    /// The data would exceed the size limit of the builder.
+    LimitExceeded    = 2,
+    /// A policy has rejected the data.
+    PolicyViolated   = 3,
+    /// The data look binary.
+    LooksBinary      = 4,
+    /// The memory cannot be allocated.
+    AllocationFailed = 5,
+    /// The data end in the middle of a character.
+    Incomplete       = 6,
+    /// The handle does not refer to a live builder.
+    BadHandle        = -1,
+}
+
+impl From<Utf8Error> for ResultCode {
+    #[inline]
+    fn from(error: Utf8Error) -> Self {
+        match error {
+            Utf8Error::Invalid => ResultCode::Invalid,
+            Utf8Error::Incomplete {
+                ..
+            } => ResultCode::Incomplete,
+            Utf8Error::LimitExceeded => ResultCode::LimitExceeded,
+            Utf8Error::PolicyViolated => ResultCode::PolicyViolated,
+            Utf8Error::LooksBinary => ResultCode::LooksBinary,
+            Utf8Error::AllocationFailed => ResultCode::AllocationFailed,
+        }
+    }
+}
+
+/// A table of builders addressed by integer handles. All arguments and results are plain integers or owned buffers, so it can back the exports of a WebAssembly component (e.g. on `wasm32-wasi`) without any borrow crossing the boundary.
+///
+/// The slots of finished or discarded builders are reused. A handle carries the generation of its slot in the high 32 bits, so a stale handle is rejected with `ResultCode::BadHandle` instead of reaching the builder which reuses the slot.
+#[derive(Debug, Clone, Default)]
+pub struct Utf8Handles {
+    slots: Vec<Slot>,
+    free:  Vec<u32>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Slot {
+    /// incremented whenever the builder of the slot is released
+    generation: u32,
+    builder:    Option<Utf8Builder>,
+}
+
+impl Utf8Handles {
+    /// Constructs a new, empty `Utf8Handles`.
+    #[inline]
+    pub const fn new() -> Self {
+        Utf8Handles {
+            slots: Vec::new(), free: Vec::new()
+        }
+    }
+
+    /// Creates a builder and returns its handle.
+    pub fn create(&mut self) -> u64 {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot::default());
+
+                (self.slots.len() - 1) as u32
+            },
+        };
+
+        let slot = &mut self.slots[index as usize];
+
+        slot.builder = Some(Utf8Builder::new());
+
+        (slot.generation as u64) << 32 | index as u64
+    }
+
+    /// Pushes a chunk into a builder.
+    #[inline]
+    pub fn push(&mut self, handle: u64, chunk: Vec<u8>) -> ResultCode {
+        match self.get_mut(handle) {
+            Some(builder) => match builder.push_chunk(&chunk) {
+                Ok(_) => ResultCode::Ok,
+                Err(err) => err.into(),
+            },
+            None => ResultCode::BadHandle,
+        }
+    }
+
+    /// Sets the maximum number of bytes of a builder. `0` means no limit.
+    #[inline]
+    pub fn set_limit(&mut self, handle: u64, limit: u32) -> ResultCode {
+        match self.get_mut(handle) {
+            Some(builder) => {
+                builder.set_limit(if limit == 0 { None } else { Some(limit as usize) });
+
+                ResultCode::Ok
+            },
+            None => ResultCode::BadHandle,
+        }
+    }
+
+    /// Finishes a builder and returns its `String`. The handle is released whether it succeeds or not.
+    #[inline]
+    pub fn finish(&mut self, handle: u64) -> Result<String, ResultCode> {
+        match self.take(handle) {
+            Some(builder) => builder.finalize().map_err(ResultCode::from),
+            None => Err(ResultCode::BadHandle),
+        }
+    }
+
+    /// Discards a builder and releases its handle.
+    #[inline]
+    pub fn discard(&mut self, handle: u64) -> ResultCode {
+        match self.take(handle) {
+            Some(_) => ResultCode::Ok,
+            None => ResultCode::BadHandle,
+        }
+    }
+
+    /// Returns the number of live builders.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Returns `true` if there is no live builder.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the slot of a handle whose generation is current.
+    #[inline]
+    fn slot_mut(&mut self, handle: u64) -> Option<&mut Slot> {
+        let slot = self.slots.get_mut(handle as u32 as usize)?;
+
+        if slot.generation == (handle >> 32) as u32 {
+            Some(slot)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, handle: u64) -> Option<&mut Utf8Builder> {
+        self.slot_mut(handle).and_then(|slot| slot.builder.as_mut())
+    }
+
+    #[inline]
+    fn take(&mut self, handle: u64) -> Option<Utf8Builder> {
+        let slot = self.slot_mut(handle)?;
+        let builder = slot.builder.take()?;
+
+        slot.generation = slot.generation.wrapping_add(1);
+
+        self.free.push(handle as u32);
+
+        Some(builder)
+    }
+}
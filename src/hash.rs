@@ -0,0 +1,61 @@
+use alloc::string::String;
+use core::convert::Infallible;
+
+use sha2::{Digest, Sha256};
+
+use crate::{TeeSink, Utf8Builder, Utf8Error, Utf8Tee};
+
+/// A SHA-256 hasher of the complete characters. It can be used as the sink of `Utf8Builder::tee` to compute the content hash during accumulation.
+#[derive(Debug, Clone, Default)]
+pub struct Sha256Sink {
+    hasher: Sha256,
+}
+
+impl Sha256Sink {
+    /// Constructs a new `Sha256Sink`.
+    #[inline]
+    pub fn new() -> Self {
+        Sha256Sink {
+            hasher: Sha256::new()
+        }
+    }
+
+    /// Returns the hash of the written text.
+    #[inline]
+    pub fn finalize(self) -> [u8; 32] {
+        self.hasher.finalize().into()
+    }
+}
+
+impl TeeSink for Sha256Sink {
+    type Error = Infallible;
+
+    #[inline]
+    fn write_text(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.hasher.update(s.as_bytes());
+
+        Ok(())
+    }
+}
+
+impl Utf8Builder {
+    /// Wraps this builder so that the SHA-256 hash of its data is computed during accumulation. The complete characters already in the builder are hashed first.
+    #[inline]
+    pub fn hashed(self) -> Utf8Tee<Sha256Sink> {
+        let mut sink = Sha256Sink::new();
+
+        sink.hasher.update(self.as_valid_str().as_bytes());
+
+        self.tee(sink)
+    }
+}
+
+impl Utf8Tee<Sha256Sink> {
+    /// Try to get the `String` instance together with its SHA-256 hash, without another pass over the data.
+    #[inline]
+    pub fn finalize_with_hash(self) -> Result<(String, [u8; 32]), Utf8Error> {
+        let (builder, sink) = self.into_parts();
+
+        Ok((builder.finalize()?, sink.finalize()))
+    }
+}
@@ -0,0 +1,63 @@
+use core::convert::Infallible;
+
+use crate::TeeSink;
+
+/// A 256-bucket histogram of bytes. It can be used as the sink of `Utf8Builder::tee` to profile the accepted data without keeping another copy of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteHistogram {
+    counts: [u64; 256],
+}
+
+impl ByteHistogram {
+    /// Constructs a new, empty `ByteHistogram`.
+    #[inline]
+    pub const fn new() -> Self {
+        ByteHistogram {
+            counts: [0; 256]
+        }
+    }
+
+    /// Records bytes.
+    #[inline]
+    pub fn record(&mut self, bytes: &[u8]) {
+        for b in bytes.iter().copied() {
+            self.counts[b as usize] += 1;
+        }
+    }
+
+    /// Returns how many times a byte has been recorded.
+    #[inline]
+    pub fn count(&self, b: u8) -> u64 {
+        self.counts[b as usize]
+    }
+
+    /// Returns the counts of all bytes, indexed by byte values.
+    #[inline]
+    pub fn counts(&self) -> &[u64; 256] {
+        &self.counts
+    }
+
+    /// Returns the number of recorded bytes.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+}
+
+impl Default for ByteHistogram {
+    #[inline]
+    fn default() -> Self {
+        ByteHistogram::new()
+    }
+}
+
+impl TeeSink for ByteHistogram {
+    type Error = Infallible;
+
+    #[inline]
+    fn write_text(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.record(s.as_bytes());
+
+        Ok(())
+    }
+}
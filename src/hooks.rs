@@ -0,0 +1,115 @@
+use alloc::string::String;
+
+use crate::{PushError, PushOutcome, Utf8Builder, Utf8Error};
+
+/// Callbacks which are invoked by a `HookedBuilder` when a push fails, before the error is returned.
+pub trait FailureHooks {
+    /// Invoked when a push fails because the limit would be exceeded. `offset` is the number of bytes in the builder, and `additional` is the number of bytes of the push.
+    #[inline]
+    fn on_overflow(&mut self, _offset: usize, _additional: usize) {}
+
+    /// Invoked when a push rejects data and poisons the builder, with the error recorded by the builder. The bytes around the offending position are available if `Utf8Builder::set_error_context` is used.
+    #[inline]
+    fn on_invalid(&mut self, _error: &PushError) {}
+}
+
+impl<O: FnMut(usize, usize), I: FnMut(&PushError)> FailureHooks for (O, I) {
+    #[inline]
+    fn on_overflow(&mut self, offset: usize, additional: usize) {
+        (self.0)(offset, additional)
+    }
+
+    #[inline]
+    fn on_invalid(&mut self, error: &PushError) {
+        (self.1)(error)
+    }
+}
+
+/// A `Utf8Builder` which reports every failed push to `FailureHooks`. Created by `Utf8Builder::with_hooks`.
+#[derive(Debug, Clone)]
+pub struct HookedBuilder<H> {
+    builder: Utf8Builder,
+    hooks:   H,
+}
+
+impl Utf8Builder {
+    /// Wraps this builder so that every failed push from now on is reported to `hooks`.
+    #[inline]
+    pub fn with_hooks<H: FailureHooks>(self, hooks: H) -> HookedBuilder<H> {
+        HookedBuilder {
+            builder: self,
+            hooks,
+        }
+    }
+}
+
+impl<H: FailureHooks> HookedBuilder<H> {
+    /// Gets a reference to the underlying builder.
+    #[inline]
+    pub fn builder(&self) -> &Utf8Builder {
+        &self.builder
+    }
+
+    /// Gets a reference to the hooks.
+    #[inline]
+    pub fn hooks(&self) -> &H {
+        &self.hooks
+    }
+
+    /// Pushes a byte.
+    #[inline]
+    pub fn push(&mut self, b: u8) -> Result<PushOutcome, Utf8Error> {
+        self.hook_with(1, |builder| builder.push(b))
+    }
+
+    /// Pushes a `&str`.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), Utf8Error> {
+        self.hook_with(s.len(), |builder| builder.push_str(s))
+    }
+
+    /// Pushes a char.
+    #[inline]
+    pub fn push_char(&mut self, c: char) -> Result<(), Utf8Error> {
+        self.hook_with(c.len_utf8(), |builder| builder.push_char(c))
+    }
+
+    /// Pushes a chunk.
+    #[inline]
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<PushOutcome, Utf8Error> {
+        self.hook_with(chunk.len(), |builder| builder.push_chunk(chunk))
+    }
+
+    /// Try to get the `String` instance.
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        self.builder.finalize()
+    }
+
+    /// Consumes this `HookedBuilder`, returning the builder and the hooks.
+    #[inline]
+    pub fn into_parts(self) -> (Utf8Builder, H) {
+        (self.builder, self.hooks)
+    }
+
+    fn hook_with<T>(
+        &mut self,
+        additional: usize,
+        f: impl FnOnce(&mut Utf8Builder) -> Result<T, Utf8Error>,
+    ) -> Result<T, Utf8Error> {
+        let poisoned = self.builder.error().is_some();
+
+        let offset = self.builder.len();
+
+        f(&mut self.builder).map_err(|err| {
+            match self.builder.error() {
+                // a poisoned builder has been reported
+                Some(error) if !poisoned => self.hooks.on_invalid(error),
+                _ if err == Utf8Error::LimitExceeded => self.hooks.on_overflow(offset, additional),
+                _ => (),
+            }
+
+            err
+        })
+    }
+}
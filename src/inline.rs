@@ -0,0 +1,180 @@
+use alloc::string::String;
+
+use crate::{state, PushOutcome, Utf8Builder, Utf8Error, Utf8State};
+
+#[derive(Debug, Clone)]
+enum Storage<const N: usize> {
+    Inline { buffer: [u8; N], len: usize, state: Utf8State },
+    Heap(Utf8Builder),
+}
+
+/// A builder which stores up to `N` bytes inline, and only spills them into a `Utf8Builder` on the heap when more bytes are pushed or a push fails. It suits lots of short data, e.g. header values.
+#[derive(Debug, Clone)]
+pub struct InlineBuilder<const N: usize> {
+    storage: Storage<N>,
+}
+
+impl<const N: usize> InlineBuilder<N> {
+    /// Constructs a new, empty `InlineBuilder`.
+    #[inline]
+    pub const fn new() -> Self {
+        InlineBuilder {
+            storage: Storage::Inline {
+                buffer: [0; N], len: 0, state: Utf8State::new()
+            },
+        }
+    }
+
+    /// Returns `true` if the data are still stored inline.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.storage, Storage::Inline { .. })
+    }
+
+    /// Returns the length of the data, in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline {
+                len, ..
+            } => *len,
+            Storage::Heap(builder) => builder.len(),
+        }
+    }
+
+    /// Returns `true` if there are no data.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns whether the current data are valid UTF-8
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        match &self.storage {
+            Storage::Inline {
+                state, ..
+            } => state.is_valid(),
+            Storage::Heap(builder) => builder.is_valid(),
+        }
+    }
+
+    /// Extracts a string slice containing all complete characters. The incomplete character (if any) is excluded.
+    #[inline]
+    pub fn as_valid_str(&self) -> &str {
+        match &self.storage {
+            Storage::Inline {
+                buffer,
+                len,
+                state,
+            } => unsafe { core::str::from_utf8_unchecked(&buffer[..*len - state.pending().len()]) },
+            Storage::Heap(builder) => builder.as_valid_str(),
+        }
+    }
+
+    /// Pushes a byte.
+    #[inline]
+    pub fn push(&mut self, b: u8) -> Result<PushOutcome, Utf8Error> {
+        self.push_chunk(&[b])
+    }
+
+    /// Pushes a `&str`.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), Utf8Error> {
+        if let Storage::Inline {
+            buffer,
+            len,
+            state,
+        } = &mut self.storage
+        {
+            if state.is_valid() && s.len() <= N - *len {
+                buffer[*len..*len + s.len()].copy_from_slice(s.as_bytes());
+                *len += s.len();
+
+                return Ok(());
+            }
+        }
+
+        self.spill(s.len()).push_str(s)
+    }
+
+    /// Pushes a char.
+    #[inline]
+    pub fn push_char(&mut self, c: char) -> Result<(), Utf8Error> {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Pushes a chunk. Returns whether the data end on a character boundary afterwards.
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<PushOutcome, Utf8Error> {
+        if let Storage::Inline {
+            buffer,
+            len,
+            state,
+        } = &mut self.storage
+        {
+            if chunk.len() <= N - *len {
+                let mut validated = *state;
+
+                // an invalid chunk is pushed by a `Utf8Builder` to poison it
+                if state::validate(&mut validated, chunk).is_ok() {
+                    buffer[*len..*len + chunk.len()].copy_from_slice(chunk);
+                    *len += chunk.len();
+                    *state = validated;
+
+                    return Ok(validated.outcome());
+                }
+            }
+        }
+
+        self.spill(chunk.len()).push_chunk(chunk)
+    }
+
+    /// Try to get the `String` instance. The inline data are copied into a `String` of the exact size.
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        self.into_builder().finalize()
+    }
+
+    /// Consumes this `InlineBuilder`, returning a `Utf8Builder` with the data.
+    #[inline]
+    pub fn into_builder(self) -> Utf8Builder {
+        match self.storage {
+            Storage::Inline {
+                buffer,
+                len,
+                ..
+            } => Utf8Builder::try_from_partial(&buffer[..len]).unwrap(),
+            Storage::Heap(builder) => builder,
+        }
+    }
+
+    /// Moves the inline data into a `Utf8Builder` with room for `additional` more bytes if needed.
+    fn spill(&mut self, additional: usize) -> &mut Utf8Builder {
+        if let Storage::Inline {
+            buffer,
+            len,
+            ..
+        } = &self.storage
+        {
+            let mut builder = Utf8Builder::with_capacity(len.saturating_add(additional));
+
+            builder.push_chunk(&buffer[..*len]).unwrap();
+
+            self.storage = Storage::Heap(builder);
+        }
+
+        match &mut self.storage {
+            Storage::Heap(builder) => builder,
+            Storage::Inline {
+                ..
+            } => unreachable!(),
+        }
+    }
+}
+
+impl<const N: usize> Default for InlineBuilder<N> {
+    #[inline]
+    fn default() -> Self {
+        InlineBuilder::new()
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::boxed::Box;
+use std::time::{Duration, Instant};
+
+use crate::{
+    state::{self, Probe},
+    Utf8Builder, Utf8State, ValidSplit,
+};
+
+/// The accounting of a validation path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PathStats {
+    /// The number of times the path has been taken.
+    pub calls: u64,
+    /// The number of bytes validated by the path.
+    pub bytes: u64,
+    /// The time spent on the path.
+    pub time:  Duration,
+}
+
+impl PathStats {
+    #[inline]
+    fn record(&mut self, bytes: usize, time: Duration) {
+        self.calls += 1;
+        self.bytes += bytes as u64;
+        self.time += time;
+    }
+}
+
+/// The time and bytes spent on every validation path of the pushes of a `Utf8Builder`. Enabled by `Utf8Builder::set_validation_profiling`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationProfile {
+    /// Completing the incomplete characters from the previous chunks.
+    pub stitch:    PathStats,
+    /// Skipping the ASCII runs with SSE2 intrinsics. Only taken with the `sse2` feature on x86_64.
+    pub ascii:     PathStats,
+    /// Validating the rest of the data, i.e. the multi-byte runs between the ASCII runs with the `sse2` feature, or everything after the completed characters without it.
+    pub multibyte: PathStats,
+}
+
+/// Times the paths reported by `state::validate_with` into a profile.
+struct Timer<'a> {
+    profile: &'a mut ValidationProfile,
+    start:   Instant,
+}
+
+impl<'a> Probe for Timer<'a> {
+    #[inline]
+    fn enter(&mut self) {
+        self.start = Instant::now();
+    }
+
+    #[inline]
+    fn stitch(&mut self, bytes: usize) {
+        self.profile.stitch.record(bytes, self.start.elapsed());
+    }
+
+    #[inline]
+    fn ascii(&mut self, bytes: usize) {
+        self.profile.ascii.record(bytes, self.start.elapsed());
+    }
+
+    #[inline]
+    fn multibyte(&mut self, bytes: usize) {
+        self.profile.multibyte.record(bytes, self.start.elapsed());
+    }
+}
+
+impl ValidationProfile {
+    /// Validates a chunk with `state::validate_with`, recording every path taken.
+    #[inline]
+    pub(crate) fn validate(
+        &mut self,
+        state: &mut Utf8State,
+        chunk: &[u8],
+    ) -> Result<ValidSplit, usize> {
+        state::validate_with(state, chunk, &mut Timer {
+            profile: self, start: Instant::now()
+        })
+    }
+}
+
+impl Utf8Builder {
+    /// Enables or disables recording the time and bytes spent on every validation path of the pushes. Disabling it discards the recorded profile.
+    #[inline]
+    pub fn set_validation_profiling(&mut self, enabled: bool) {
+        if !enabled {
+            self.validation_profile = None;
+        } else if self.validation_profile.is_none() {
+            self.validation_profile = Some(Box::default());
+        }
+    }
+
+    /// Returns the recorded profile of the validation paths, if it is enabled.
+    #[inline]
+    pub fn validation_profile(&self) -> Option<&ValidationProfile> {
+        self.validation_profile.as_deref()
+    }
+}
@@ -0,0 +1,18 @@
+use core::hash::BuildHasher;
+
+use lasso::{Key, Rodeo};
+
+use crate::{Utf8Builder, Utf8Error};
+
+impl Utf8Builder {
+    /// Try to intern the data into a `lasso::Rodeo` directly from the buffer, without producing a `String`. Returns the key of the interned string.
+    #[inline]
+    pub fn finalize_interned<K: Key, S: BuildHasher>(
+        self,
+        interner: &mut Rodeo<K, S>,
+    ) -> Result<K, Utf8Error> {
+        self.check_finalize()?;
+
+        Ok(interner.get_or_intern(self.as_valid_str()))
+    }
+}
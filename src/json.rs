@@ -0,0 +1,61 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::{Utf8Builder, Utf8Error};
+
+impl Utf8Builder {
+    /// Try to get the `String` instance with JSON string escaping applied. The surrounding quotation marks are not added.
+    #[inline]
+    pub fn finalize_json_escaped(self) -> Result<String, Utf8Error> {
+        self.check_finalize()?;
+
+        let mut s = String::with_capacity(self.len());
+
+        self.write_json_escaped(&mut s).unwrap();
+
+        Ok(s)
+    }
+
+    /// Writes the complete characters with JSON string escaping applied into a `fmt::Write`. The surrounding quotation marks are not added.
+    #[inline]
+    pub fn write_json_escaped<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        escape_json(self.as_valid_str(), writer)
+    }
+}
+
+fn escape_json<W: Write>(s: &str, writer: &mut W) -> fmt::Result {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let bytes = s.as_bytes();
+
+    let mut start = 0;
+
+    for (i, b) in bytes.iter().copied().enumerate() {
+        // only ASCII characters need to be escaped, so `i` is always on a character boundary here
+        let escaped = match b {
+            b'"' => "\\\"",
+            b'\\' => "\\\\",
+            b'\n' => "\\n",
+            b'\r' => "\\r",
+            b'\t' => "\\t",
+            0x08 => "\\b",
+            0x0C => "\\f",
+            0x00..=0x1F => "",
+            _ => continue,
+        };
+
+        writer.write_str(&s[start..i])?;
+
+        if escaped.is_empty() {
+            let buf = [b'\\', b'u', b'0', b'0', HEX[(b >> 4) as usize], HEX[(b & 0xF) as usize]];
+
+            writer.write_str(unsafe { core::str::from_utf8_unchecked(&buf) })?;
+        } else {
+            writer.write_str(escaped)?;
+        }
+
+        start = i + 1;
+    }
+
+    writer.write_str(&s[start..])
+}
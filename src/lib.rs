@@ -36,62 +36,509 @@ Disable the default features to compile this crate without std.
 version = "*"
 default-features = false
 ```
+
+## Const Validation
+
+Enable the `const-validation` feature to use `validate_const`, `validate_slice_const` and `str_from_static`, which validate baked-in byte tables at compile time with the same rules as `Utf8Builder`. This API is unstable.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["const-validation"]
+```
+
+## Differential Testing
+
+Enable the `diff-test` feature to cross-check the data against `core::str::from_utf8` after every push in debug builds. If the results differ, it panics with a dump of the data to reproduce the problem.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["diff-test"]
+```
+
+## Handles
+
+Enable the `handles` feature to use `Utf8Handles`, a table of builders addressed by integer handles whose operations take and return only integers and owned buffers, which suits the exports of a WebAssembly component.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["handles"]
+```
+
+## Nom
+
+Enable the `nom` feature to convert a `StreamingError` into a `nom::Err`, so that an incomplete character becomes `nom::Err::Incomplete` with the needed size.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["nom"]
+```
+
+## Lasso
+
+Enable the `lasso` feature to use `Utf8Builder::finalize_interned`, which interns the data into a `lasso::Rodeo` directly from the buffer of the builder, without producing a `String` for every token.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["lasso"]
+```
+
+## Log
+
+Enable the `log` feature to use `Utf8Logger`, a `log::Log` which captures the records into an in-memory `Utf8Builder` with a size cap, dropping the oldest lines when it is full. It works without `std`, so it suits capturing logs on embedded targets to be dumped on a fault.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["log"]
+```
+
+## No Panic
+
+Enable the `no-panic` feature to make `push`, `push_str`, `push_char`, `push_chunk`, `push_repeat_char`, `push_str_lossy_from_bytes` and `extend_from_within` of `Utf8Builder` fail with `Utf8Error::AllocationFailed` instead of panicking when the buffer cannot grow, without poisoning the builder, and make `extend_from_within` fail with `Utf8Error::Invalid` instead of panicking when the range is not on the complete characters. Use `try_reserve` to reserve capacity fallibly, because `reserve` and `reserve_for_chars` silently discard the failure. Only these methods are covered. The other methods and types of this crate may still panic or abort when memory runs out, and so do the covered methods when they copy a buffer still shared with a `Utf8Snapshot` or capture the context of a failed push. This feature requires Rust 1.57.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["no-panic"]
+```
+
+## SSE2
+
+On x86_64, enable the `sse2` feature to skip every ASCII run of a chunk with SSE2 intrinsics, so that only the multi-byte characters between them are validated by the scalar validation, on a stable toolchain and without any extra dependency. It is ignored on the other targets.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["sse2"]
+```
+
+## Validation Profile
+
+Enable the `validation-profile` feature to use `Utf8Builder::set_validation_profiling`, which records the time and bytes spent on every validation path of the pushes (completing incomplete characters, skipping ASCII runs with the `sse2` feature, and validating the rest) into a `ValidationProfile`, for tuning the chunk sizes.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["validation-profile"]
+```
+
+## Zeroize
+
+Enable the `zeroize` feature to make `Utf8Builder` wipe its memory, including the old allocations replaced while growing, when it is dropped. Use `finalize_zeroizing` to get a `Zeroizing<String>`.
+
+A buffer shared with `Utf8Snapshot`s is wiped by whichever owner drops it last. The data handed out by `finalize`, `into_bytes`, `leak` and `seal` are not wiped by the builder, but `leak` wipes the allocation it replaces instead of shrinking it.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["zeroize"]
+```
+
+## Script Tally
+
+Enable the `script` feature to use `ScriptTally`, which counts characters by their Unicode scripts as they complete when used as the sink of `Utf8Builder::tee`.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["script"]
+```
+
+## SHA-256
+
+Enable the `sha2` feature to use `Utf8Builder::hashed`, which computes the SHA-256 hash of the data during accumulation, so that `finalize_with_hash` returns the `String` together with its content hash.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["sha2"]
+```
+
+## Stream
+
+Enable the `stream` feature to use `Utf8Stream`, which turns a `Stream` of byte chunks into a `Stream` of `String`s containing only complete characters, and `Utf8Builder::accumulate_with_deadline`, which collects a `Stream` of byte chunks into a `String` unless a deadline future of any runtime completes first.
+
+```toml
+[dependencies.utf8-builder]
+version = "*"
+features = ["stream"]
+```
 */
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 
+mod binary;
+mod char_index;
+mod chunks;
+mod conformance;
+#[cfg(feature = "const-validation")]
+mod const_validate;
+mod convert;
+mod cow;
+#[cfg(feature = "stream")]
+mod deadline;
+mod decoder;
+mod demux;
+#[cfg(all(feature = "diff-test", debug_assertions))]
+mod diff_test;
 mod error;
+mod extend;
+#[cfg(feature = "handles")]
+mod handle;
+#[cfg(feature = "sha2")]
+mod hash;
+mod histogram;
+mod hooks;
+mod inline;
+#[cfg(feature = "validation-profile")]
+mod instrument;
+#[cfg(feature = "lasso")]
+mod interner;
+mod json;
+mod lines;
+#[cfg(feature = "log")]
+mod logger;
+mod lossy;
+mod matches;
+mod merge;
+#[cfg(feature = "std")]
+mod observe;
+mod parse;
+mod percent;
+#[cfg(feature = "std")]
+mod pipe;
+#[cfg(feature = "std")]
+mod pipeline;
+mod profile;
+#[cfg(feature = "std")]
+mod reader;
+mod reassemble;
+mod recovery;
+mod redact;
+mod retain;
+#[cfg(feature = "script")]
+mod script;
+mod seal;
+mod segmented;
+mod shared;
+#[cfg(feature = "std")]
+mod snapshot;
+mod split;
+mod sse;
+mod state;
+#[cfg(feature = "stream")]
+mod stream;
+mod streaming;
+mod tail;
+mod tee;
+mod transform;
+mod websocket;
+mod wide;
+#[cfg(feature = "std")]
+mod writer;
+mod yielding;
 
 use alloc::{string::String, vec::Vec};
-use core::cmp::Ordering;
+use core::{
+    ops::{Bound, Index, RangeBounds},
+    slice::SliceIndex,
+};
+#[cfg(feature = "std")]
+use std::sync::Arc;
 
-pub use error::Utf8Error;
+pub use binary::BinaryDetection;
+use binary::BinarySample;
+pub use char_index::CharIndexedBuilder;
+pub use chunks::StrChunks;
+pub use conformance::{check_conformance, Strictness, Violation, ViolationKind};
+#[cfg(feature = "const-validation")]
+pub use const_validate::{str_from_static, validate_const, validate_slice_const};
+pub use cow::CowBuilder;
+#[cfg(feature = "stream")]
+pub use deadline::{AccumulateError, AccumulateFailure, AccumulateWithDeadline};
+pub use decoder::{CoderResult, DecoderResult, Utf8Decoder};
+pub use demux::Utf8Demux;
+pub use error::{ErrorContext, PushError, Utf8Error};
+pub use extend::{ExtendError, TryExtend};
+#[cfg(feature = "handles")]
+pub use handle::{ResultCode, Utf8Handles};
+#[cfg(feature = "sha2")]
+pub use hash::Sha256Sink;
+pub use histogram::ByteHistogram;
+pub use hooks::{FailureHooks, HookedBuilder};
+pub use inline::InlineBuilder;
+#[cfg(feature = "validation-profile")]
+pub use instrument::{PathStats, ValidationProfile};
+#[cfg(feature = "log")]
+pub use logger::Utf8Logger;
+pub use lossy::Diverted;
+pub use matches::MatchCounter;
+#[cfg(feature = "std")]
+pub use observe::{ObservedBuilder, PushObserver, PushStats, ThroughputPolicy};
+pub use parse::ParseError;
+pub use percent::{AsciiSet, PercentEncodeSink};
+#[cfg(feature = "std")]
+pub use pipe::{PipeStats, Utf8Pipe};
+#[cfg(feature = "std")]
+pub use pipeline::PipelinedBuilder;
+pub use profile::{BomPolicy, Profile};
+#[cfg(feature = "std")]
+pub use reader::BytesReader;
+pub use reassemble::Utf8Reassembler;
+pub use recovery::{ErrorRecovery, Segment};
+pub use redact::Utf8Redactor;
+pub use retain::RetainLines;
+#[cfg(feature = "script")]
+pub use script::ScriptTally;
+pub use seal::SealedUtf8;
+pub use segmented::SegmentedBuilder;
+pub use shared::SharedStr;
+#[cfg(feature = "std")]
+use snapshot::SharedBuffer;
+#[cfg(feature = "std")]
+pub use snapshot::Utf8Snapshot;
+pub use split::{SplitTerminatorDrain, SplitWhitespaceStream};
+pub use sse::SseSplitter;
+pub use state::{validate_and_count, validate_chunk, CharLen, PushOutcome, Utf8State, ValidSplit};
+#[cfg(feature = "stream")]
+pub use stream::Utf8Stream;
+pub use streaming::{validate_streaming, StreamingError};
+pub use tail::TailBuilder;
+#[cfg(feature = "std")]
+pub use tee::IoSink;
+pub use tee::{FmtSink, TeeError, TeeSink, Utf8Tee};
+pub use transform::{
+    CaseFold, CollapseWhitespace, NormalizeNewlines, Stage, StripAnsi, TransformPipeline,
+};
+#[cfg(feature = "script")]
+pub use unicode_script::Script;
+pub use websocket::{WebSocketTextError, WebSocketTextValidator};
+pub use wide::WideBuilder;
+#[cfg(feature = "std")]
+pub use writer::FinalizeError;
+pub use yielding::YieldingPush;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// The maximum number of bytes reserved by `Utf8Builder::with_size_hint`.
+const MAX_HINT_RESERVATION: usize = 64 * 1024;
 
 /// A builder for Building and validating UTF-8 data from chunks.
 #[derive(Debug, Clone, Default)]
 pub struct Utf8Builder {
-    buffer: Vec<u8>,
-    /// the length for the incomplete character
-    sl:     u8,
-    /// the valid expected length for the incomplete character
-    sel:    u8,
+    buffer:             Vec<u8>,
+    /// the state for the incomplete character
+    state:              Utf8State,
+    /// the data moved out by `freeze`, which are taken back (or copied if a snapshot is still alive) before being mutated
+    #[cfg(feature = "std")]
+    frozen:             Option<Arc<SharedBuffer>>,
+    /// the policy for the complete characters
+    profile:            Profile,
+    /// the policy for the BOMs which are not at the beginning
+    bom_policy:         BomPolicy,
+    /// the maximum number of bytes
+    limit:              Option<usize>,
+    /// the error of the first failed push, which makes the later pushes fail as well
+    error:              Option<PushError>,
+    /// the number of bytes captured on each side of the offending position of a failed push
+    context_len:        usize,
+    /// the heuristic which tells binary data from text, if enabled
+    binary_detection:   Option<BinaryDetection>,
+    /// the bytes inspected by the binary detection so far
+    binary_sample:      BinarySample,
+    /// the time and bytes spent on every validation path, if enabled
+    #[cfg(feature = "validation-profile")]
+    validation_profile: Option<alloc::boxed::Box<ValidationProfile>>,
 }
 
 impl Utf8Builder {
-    /// Constructs a new, empty `Utf8Builder`.
+    /// Constructs a new `Utf8Builder` with a buffer which must be valid UTF-8.
     #[inline]
-    pub const fn new() -> Self {
+    const fn from_buffer(buffer: Vec<u8>) -> Self {
         Utf8Builder {
-            buffer: Vec::new(), sl: 0, sel: 0
+            buffer,
+            state: Utf8State::new(),
+            #[cfg(feature = "std")]
+            frozen: None,
+            profile: Profile::Freeform,
+            bom_policy: BomPolicy::Keep,
+            limit: None,
+            error: None,
+            context_len: 0,
+            binary_detection: None,
+            binary_sample: BinarySample::new(),
+            #[cfg(feature = "validation-profile")]
+            validation_profile: None,
         }
     }
 
+    /// Constructs a new, empty `Utf8Builder`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::from_buffer(Vec::new())
+    }
+
     /// Constructs a new, empty `with_capacity` with a specific capacity.
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
-        Utf8Builder {
-            buffer: Vec::with_capacity(capacity), sl: 0, sel: 0
+        Self::from_buffer(Vec::with_capacity(capacity))
+    }
+
+    /// Constructs a new, empty `Utf8Builder` with an untrusted size hint (e.g. `Content-Length`) and an optional hard limit of bytes. The reservation is clamped by the hard limit and by 64 KiB, so a forged hint cannot make the builder allocate a huge buffer up front.
+    #[inline]
+    pub fn with_size_hint(hint: usize, hard_limit: Option<usize>) -> Self {
+        let mut capacity = hint.min(MAX_HINT_RESERVATION);
+
+        if let Some(limit) = hard_limit {
+            capacity = capacity.min(limit);
         }
+
+        let mut builder = Self::with_capacity(capacity);
+
+        builder.limit = hard_limit;
+
+        builder
+    }
+
+    /// Constructs a new `Utf8Builder` from a byte slice. Unlike `core::str::from_utf8`, an incomplete character at the end of the slice is accepted and kept as the incomplete character of the builder.
+    #[inline]
+    pub fn try_from_partial(bytes: &[u8]) -> Result<Self, Utf8Error> {
+        let mut builder = Utf8Builder::with_capacity(bytes.len());
+
+        builder.push_chunk(bytes)?;
+
+        Ok(builder)
     }
 
     /// Reserves capacity for at least `additional` more elements to be inserted in the given `Utf8Builder`.
+    ///
+    /// If the `no-panic` feature is enabled, an allocation failure is silently discarded, so nothing may have been reserved when this returns. Use `try_reserve` to find out.
     #[inline]
     pub fn reserve(&mut self, additional: usize) {
-        self.buffer.reserve(additional);
+        let _ = self.grow(additional);
+    }
+
+    /// Tries to reserve capacity for at least `additional` more elements to be inserted in the given `Utf8Builder`. If the `no-panic` feature is enabled, an allocation failure is returned as `Utf8Error::AllocationFailed` instead of panicking.
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), Utf8Error> {
+        self.grow(additional)
+    }
+
+    /// Reserves capacity for at least `n_chars` more characters whose average width is `avg_width` bytes (clamped to 1..=4). The worst case of 4 bytes per character is used if `avg_width` is `None`.
+    ///
+    /// Like `reserve`, an allocation failure is silently discarded if the `no-panic` feature is enabled. Use `try_reserve` to find out.
+    #[inline]
+    pub fn reserve_for_chars(&mut self, n_chars: usize, avg_width: Option<usize>) {
+        let width = avg_width.map_or(4, |w| w.clamp(1, 4));
+
+        let _ = self.grow(n_chars.saturating_mul(width));
+    }
+
+    /// Returns the number of bytes the builder can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
     }
 
     /// Returns the number of elements in the buffer.
     #[inline]
     pub fn len(&self) -> usize {
-        self.buffer.len()
+        self.bytes().len()
     }
 
     /// Returns `true` if the builder contains no data.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.buffer.is_empty()
+        self.bytes().is_empty()
+    }
+
+    /// Makes sure that at least `additional` more bytes can be pushed without reallocation. If the `no-panic` feature is enabled, an allocation failure is returned as `Utf8Error::AllocationFailed` instead of panicking.
+    #[inline]
+    #[cfg_attr(feature = "no-panic", clippy::msrv = "1.57")]
+    fn grow(&mut self, additional: usize) -> Result<(), Utf8Error> {
+        self.thaw();
+
+        #[cfg(feature = "zeroize")]
+        if self.buffer.capacity() - self.buffer.len() < additional {
+            // reallocate by ourselves so that the old allocation can be wiped before being freed
+            let capacity = self
+                .buffer
+                .len()
+                .saturating_add(additional)
+                .max(self.buffer.capacity().saturating_mul(2));
+
+            let mut buffer = Vec::new();
+
+            #[cfg(feature = "no-panic")]
+            buffer.try_reserve_exact(capacity).map_err(|_| Utf8Error::AllocationFailed)?;
+
+            #[cfg(not(feature = "no-panic"))]
+            buffer.reserve_exact(capacity);
+
+            buffer.extend_from_slice(&self.buffer);
+
+            self.buffer.zeroize();
+            self.buffer = buffer;
+        }
+
+        #[cfg(all(not(feature = "zeroize"), feature = "no-panic"))]
+        self.buffer.try_reserve(additional).map_err(|_| Utf8Error::AllocationFailed)?;
+
+        #[cfg(all(not(feature = "zeroize"), not(feature = "no-panic")))]
+        self.buffer.reserve(additional);
+
+        Ok(())
+    }
+
+    /// Returns all the data, including the incomplete character.
+    #[inline]
+    pub(crate) fn bytes(&self) -> &[u8] {
+        #[cfg(feature = "std")]
+        if let Some(data) = self.frozen.as_ref() {
+            return &data.0;
+        }
+
+        &self.buffer
+    }
+
+    /// Takes back the data moved out by `freeze`.
+    #[inline]
+    fn thaw(&mut self) {
+        #[cfg(feature = "std")]
+        if let Some(data) = self.frozen.take() {
+            self.buffer =
+                Arc::try_unwrap(data).map(SharedBuffer::into_inner).unwrap_or_else(|data| {
+                    let mut buffer = Vec::with_capacity(data.0.capacity());
+
+                    buffer.extend_from_slice(&data.0);
+
+                    buffer
+                });
+        }
+    }
+
+    /// Replaces the buffer, wiping the old allocation if the `zeroize` feature is enabled.
+    #[inline]
+    fn replace_buffer(&mut self, buffer: Vec<u8>) {
+        self.thaw();
+
+        #[cfg(feature = "zeroize")]
+        self.buffer.zeroize();
+
+        self.buffer = buffer;
+    }
+
+    /// Returns the number of bytes which belong to complete characters.
+    #[inline]
+    fn valid_len(&self) -> usize {
+        self.bytes().len() - self.state.pending().len()
     }
 }
 
@@ -99,176 +546,828 @@ impl Utf8Builder {
     /// Returns whether the current data are valid UTF-8
     #[inline]
     pub fn is_valid(&self) -> bool {
-        self.sl == 0
+        self.state.is_valid()
+    }
+
+    /// Returns the number of complete characters.
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        self.as_valid_str().chars().count()
+    }
+
+    /// Creates an immutable snapshot of the complete characters. The data are shared with the snapshot instead of being copied, and they are copied only if the builder is mutated while the snapshot is still alive.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn freeze(&mut self) -> Utf8Snapshot {
+        let len = self.valid_len();
+
+        let data = match self.frozen.as_ref() {
+            Some(data) => data.clone(),
+            None => {
+                let data = Arc::new(SharedBuffer(core::mem::take(&mut self.buffer)));
+
+                self.frozen = Some(data.clone());
+
+                data
+            },
+        };
+
+        Utf8Snapshot {
+            data,
+            len,
+        }
+    }
+
+    /// Returns the profile which restricts the accepted characters.
+    #[inline]
+    pub fn profile(&self) -> Profile {
+        self.profile
+    }
+
+    /// Sets the profile which restricts the accepted characters. It only applies to the characters completed afterwards.
+    #[inline]
+    pub fn set_profile(&mut self, profile: Profile) {
+        self.profile = profile;
+    }
+
+    /// Returns the policy for the BOMs which are not at the beginning.
+    #[inline]
+    pub fn bom_policy(&self) -> BomPolicy {
+        self.bom_policy
+    }
+
+    /// Sets the policy for the BOMs which are not at the beginning. It only applies to the characters completed afterwards.
+    #[inline]
+    pub fn set_bom_policy(&mut self, bom_policy: BomPolicy) {
+        self.bom_policy = bom_policy;
+    }
+
+    /// Returns the maximum number of bytes which can be pushed into the builder.
+    #[inline]
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Sets the maximum number of bytes which can be pushed into the builder. A push which would exceed the limit fails with `Utf8Error::LimitExceeded` without pushing anything.
+    #[inline]
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
+    /// Returns the error of the first push which rejected data. Once a push rejects data, the builder is poisoned, and the later pushes and `finalize` fail with the same error until `clear_error` is called. Exceeding the limit does not poison the builder.
+    #[inline]
+    pub fn error(&self) -> Option<&PushError> {
+        self.error.as_ref()
+    }
+
+    /// Clears the error of the failed push, so that the builder accepts data again. The rejected data are not restored, and the incomplete character (if any) is kept.
+    #[inline]
+    pub fn clear_error(&mut self) {
+        self.error = None;
+    }
+
+    /// Returns the number of bytes captured on each side of the offending position into the error of a failed push.
+    #[inline]
+    pub fn error_context(&self) -> usize {
+        self.context_len
+    }
+
+    /// Sets the number of bytes captured on each side of the offending position into the error of a failed push. No context is captured if it is 0, which is the default.
+    #[inline]
+    pub fn set_error_context(&mut self, len: usize) {
+        self.context_len = len;
+    }
+
+    /// Returns the validation state, which records the incomplete character (if any).
+    #[inline]
+    pub fn state(&self) -> &Utf8State {
+        &self.state
+    }
+
+    /// Returns whether the data end with an incomplete character which is a prefix of `expected`, e.g. the UTF-8 encoding of a specific character.
+    #[inline]
+    pub fn ends_pending_with(&self, expected: &[u8]) -> bool {
+        let pending = self.state.pending();
+
+        !pending.is_empty() && expected.starts_with(pending)
     }
 
     /// Try to get the `String` instance.
     #[inline]
-    pub fn finalize(self) -> Result<String, Utf8Error> {
-        if self.is_valid() {
-            let s = unsafe { String::from_utf8_unchecked(self.buffer) };
+    pub fn finalize(mut self) -> Result<String, Utf8Error> {
+        self.check_finalize()?;
+
+        self.thaw();
+
+        let s = unsafe { String::from_utf8_unchecked(core::mem::take(&mut self.buffer)) };
+
+        Ok(s)
+    }
+
+    /// Try to append the data onto `dst` without producing a `String`. Nothing is appended if the data are not valid.
+    #[inline]
+    pub fn finalize_extend(self, dst: &mut Vec<u8>) -> Result<(), Utf8Error> {
+        self.check_finalize()?;
+
+        dst.extend_from_slice(self.bytes());
+
+        Ok(())
+    }
+
+    /// Gets the raw buffer whether the data are valid or not, along with the validation state of its incomplete character. The bytes rejected by a failed push are not included.
+    #[inline]
+    pub fn into_bytes(mut self) -> (Vec<u8>, Utf8State) {
+        self.thaw();
 
-            Ok(s)
+        (core::mem::take(&mut self.buffer), self.state)
+    }
+
+    /// Try to get the `String` instance and leak it as a `&'static str`, like `String::leak`. The memory will never be freed.
+    #[inline]
+    pub fn leak(self) -> Result<&'static str, Utf8Error> {
+        let s = self.finalize()?;
+
+        // copy the data into an exact allocation by ourselves so that the old one can be wiped instead of being shrunk
+        #[cfg(feature = "zeroize")]
+        let s = if s.capacity() > s.len() {
+            let mut old = s;
+            let s = String::from(old.as_str());
+
+            old.zeroize();
+
+            s
         } else {
-            Err(Utf8Error)
+            s
+        };
+
+        Ok(alloc::boxed::Box::leak(s.into_boxed_str()))
+    }
+
+    /// Try to get the `String` instance, which will be wiped when it is dropped.
+    #[cfg(feature = "zeroize")]
+    #[inline]
+    pub fn finalize_zeroizing(self) -> Result<Zeroizing<String>, Utf8Error> {
+        self.finalize().map(Zeroizing::new)
+    }
+
+    /// Extracts a string slice containing all complete characters. The incomplete character (if any) is excluded.
+    #[inline]
+    pub fn as_valid_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(&self.bytes()[..self.valid_len()]) }
+    }
+
+    /// Extracts a string slice of all data, only if `finalize` would succeed, i.e. no incomplete character is pending and no push has failed.
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self.check_finalize() {
+            Ok(()) => Some(self.as_valid_str()),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns a subslice of the complete characters, like `str::get`. `None` is returned if the range is out of the complete characters or not on character boundaries.
+    #[inline]
+    pub fn get<I: SliceIndex<str>>(&self, index: I) -> Option<&I::Output> {
+        self.as_valid_str().get(index)
+    }
+
+    /// Returns a mutable reference to the underlying buffer, like `String::as_mut_vec`.
+    ///
+    /// # Safety
+    ///
+    /// The bytes before the incomplete character must stay valid UTF-8, and the last `self.state().pending().len()` bytes must stay the same as `self.state().pending()`, when the reference is released. Otherwise, the builder may produce an invalid `String`.
+    #[inline]
+    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8> {
+        self.thaw();
+
+        &mut self.buffer
+    }
+
+    /// Extracts a string slice containing at most the first `n_chars` complete characters. Only the returned characters are scanned.
+    #[inline]
+    pub fn preview(&self, n_chars: usize) -> &str {
+        let s = self.as_valid_str();
+
+        match s.char_indices().nth(n_chars) {
+            Some((end, _)) => &s[..end],
+            None => s,
         }
     }
+
+    /// Checks whether `index` is a character boundary of the complete characters, like `str::is_char_boundary`. The end of the complete characters is a boundary, and the indices beyond are not.
+    #[inline]
+    pub fn is_char_boundary(&self, index: usize) -> bool {
+        self.as_valid_str().is_char_boundary(index)
+    }
+
+    /// Finds the closest character boundary of the complete characters not after `index`.
+    #[inline]
+    pub fn floor_char_boundary(&self, index: usize) -> usize {
+        let s = self.as_valid_str();
+
+        let mut index = index.min(s.len());
+
+        while !s.is_char_boundary(index) {
+            index -= 1;
+        }
+
+        index
+    }
+
+    /// Finds the closest character boundary of the complete characters not before `index`. It is the end of the complete characters if `index` is beyond it.
+    #[inline]
+    pub fn ceil_char_boundary(&self, index: usize) -> usize {
+        let s = self.as_valid_str();
+
+        let mut index = index.min(s.len());
+
+        while !s.is_char_boundary(index) {
+            index += 1;
+        }
+
+        index
+    }
+
+    /// Extracts the longest common prefix of the complete characters and `other`, which ends on a character boundary.
+    #[inline]
+    pub fn longest_common_valid_prefix(&self, other: &str) -> &str {
+        let s = self.as_valid_str();
+
+        let len = s.bytes().zip(other.bytes()).take_while(|(a, b)| a == b).count();
+
+        &s[..self.floor_char_boundary(len)]
+    }
+
+    /// Takes out all complete characters as a `String`, leaving only the incomplete character (if any) in the builder.
+    #[inline]
+    pub fn take_valid(&mut self) -> String {
+        self.take_valid_to(self.valid_len())
+    }
+
+    /// Moves the bytes of the incomplete character (if any) into `dst`, leaving the builder on a character boundary. Returns the number of the moved bytes, so `&dst[..n]` can be pushed into another builder.
+    #[inline]
+    pub fn shrink_pending_into(&mut self, dst: &mut [u8; 4]) -> usize {
+        let pending = self.state.pending();
+        let n = pending.len();
+
+        dst[..n].copy_from_slice(pending);
+
+        if n > 0 {
+            let len = self.valid_len();
+
+            self.thaw();
+
+            self.buffer.truncate(len);
+            self.state = Utf8State::new();
+        }
+
+        n
+    }
+
+    /// Moves the bytes of the incomplete character (if any) of `other` to the end of this builder, e.g. when rotating builders while a character straddles the rotation point. Returns the number of the moved bytes. If this builder rejects them, they are kept in `other`.
+    #[inline]
+    pub fn carry_from(&mut self, other: &mut Utf8Builder) -> Result<usize, Utf8Error> {
+        let mut tail = [0; 4];
+
+        let n = other.state.pending().len();
+
+        tail[..n].copy_from_slice(other.state.pending());
+
+        self.push_chunk(&tail[..n])?;
+
+        other.shrink_pending_into(&mut tail);
+
+        Ok(n)
+    }
+
+    /// Takes out the first `end` bytes. `end` must be a character boundary within the complete characters.
+    #[inline]
+    pub(crate) fn take_valid_to(&mut self, end: usize) -> String {
+        debug_assert!(end <= self.valid_len());
+
+        self.thaw();
+
+        let tail = self.buffer.split_off(end);
+
+        let buffer = core::mem::replace(&mut self.buffer, tail);
+
+        unsafe { String::from_utf8_unchecked(buffer) }
+    }
+
+    /// Removes the first `end` bytes. `end` must be a character boundary within the complete characters.
+    #[inline]
+    pub(crate) fn discard_valid_to(&mut self, end: usize) {
+        debug_assert!(end <= self.valid_len());
+
+        self.thaw();
+
+        self.buffer.drain(..end);
+    }
 }
 
 impl Utf8Builder {
-    /// Pushes a byte.
-    pub fn push(&mut self, b: u8) -> Result<(), Utf8Error> {
-        if self.sl == 0 {
-            let w = utf8_width::get_width(b);
-
-            match w {
-                0 => return Err(Utf8Error),
-                1 => {
-                    self.buffer.push(b);
-                },
-                _ => {
-                    self.buffer.push(b);
-                    self.sl = 1;
-                    self.sel = w as u8;
-                },
+    /// Pushes a byte. Returns whether the data end on a character boundary afterwards.
+    #[inline]
+    pub fn push(&mut self, b: u8) -> Result<PushOutcome, Utf8Error> {
+        self.poison_with(|builder| {
+            builder.check_limit(1)?;
+            builder.check_binary(&[b])?;
+
+            let start = builder.valid_len();
+
+            builder.grow(1)?;
+
+            if let Err(kind) = builder.validate_byte(b) {
+                return builder.poison(kind, &[b]);
             }
-        } else if self.sl + 1 == self.sel {
-            self.buffer.push(b);
 
-            self.sl = 0;
-            // self.sel = 0; // no need
-        } else {
-            self.buffer.push(b);
+            builder.buffer.push(b);
 
-            self.sl += 1;
-        }
+            builder.check_profile(start)?;
 
-        Ok(())
+            Ok(builder.state.outcome())
+        })
     }
 
     /// Pushes a `&str`.
     #[inline]
     pub fn push_str(&mut self, s: &str) -> Result<(), Utf8Error> {
-        if self.sl == 0 {
-            self.buffer.extend_from_slice(s.as_bytes());
+        self.poison_with(|builder| {
+            if !builder.state.is_valid() {
+                return builder.poison(Utf8Error::Invalid, s.as_bytes());
+            }
 
-            Ok(())
-        } else {
-            Err(Utf8Error)
-        }
+            builder.check_limit(s.len())?;
+            builder.check_binary(s.as_bytes())?;
+
+            let start = builder.valid_len();
+
+            builder.grow(s.len())?;
+            builder.buffer.extend_from_slice(s.as_bytes());
+
+            builder.check_profile(start)
+        })
     }
 
     /// Pushes a char.
     pub fn push_char(&mut self, c: char) -> Result<(), Utf8Error> {
-        if self.sl == 0 {
-            self.buffer.reserve(4);
+        let mut buf = [0; 4];
+
+        self.push_str(c.encode_utf8(&mut buf))
+    }
+
+    /// Pushes a char `n` times. The char is encoded once, and the buffer is filled by doubling the copied data.
+    pub fn push_repeat_char(&mut self, c: char, n: usize) -> Result<(), Utf8Error> {
+        let mut buf = [0; 4];
+
+        let encoded = c.encode_utf8(&mut buf).as_bytes();
 
-            let len = self.buffer.len();
+        self.poison_with(|builder| {
+            if !builder.state.is_valid() {
+                return builder.poison(Utf8Error::Invalid, encoded);
+            }
+
+            let len = encoded.len().checked_mul(n).ok_or(Utf8Error::LimitExceeded)?;
+
+            builder.check_limit(len)?;
+
+            if len == 0 {
+                return Ok(());
+            }
 
-            unsafe {
-                self.buffer.set_len(len + 4);
+            // only the repetitions within the window are inspected
+            for _ in 0..n {
+                match builder.binary_detection {
+                    Some(detection) if builder.binary_sample.inspected() < detection.window => {
+                        builder.check_binary(encoded)?
+                    },
+                    _ => break,
+                }
             }
 
-            let c = c.encode_utf8(&mut self.buffer[len..]).len();
+            let start = builder.valid_len();
+
+            builder.grow(len)?;
+            builder.buffer.extend_from_slice(encoded);
+
+            while builder.buffer.len() - start < len {
+                let copied = builder.buffer.len() - start;
 
-            unsafe {
-                self.buffer.set_len(len + c);
+                builder.buffer.extend_from_within(start..start + copied.min(len - copied));
             }
 
-            Ok(())
-        } else {
-            Err(Utf8Error)
+            builder.check_profile(start)
+        })
+    }
+
+    /// Pushes `suffix` unless the data already end with it, e.g. to make sure of a trailing newline. The data are compared in place.
+    #[inline]
+    pub fn ensure_ends_with(&mut self, suffix: &str) -> Result<(), Utf8Error> {
+        if self.error.is_none() && self.is_valid() && self.as_valid_str().ends_with(suffix) {
+            return Ok(());
         }
+
+        self.push_str(suffix)
     }
 
-    /// Pushes a chunk.
-    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<(), Utf8Error> {
-        let chunk_size = chunk.len();
+    /// Copies the complete characters in `src` to the end of the builder, like `Vec::extend_from_within`. The copied data are not validated again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of the complete characters or not on character boundaries. If the `no-panic` feature is enabled, it fails with `Utf8Error::Invalid` without poisoning the builder instead.
+    pub fn extend_from_within<R: RangeBounds<usize>>(&mut self, src: R) -> Result<(), Utf8Error> {
+        if let Some(err) = self.error.as_ref() {
+            return Err(err.kind);
+        }
 
-        if chunk_size == 0 {
-            return Ok(());
+        if !self.state.is_valid() {
+            return self.poison(Utf8Error::Invalid, &[]);
         }
 
-        let mut e = if self.sl > 0 {
-            let r = (self.sel - self.sl) as usize;
+        let start = match src.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
 
-            match r.cmp(&chunk_size) {
-                Ordering::Greater => {
-                    let sl = self.sl as usize;
-                    let nsl = sl + chunk_size;
+        let end = match src.end_bound() {
+            Bound::Included(&n) => n.saturating_add(1),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.valid_len(),
+        };
 
-                    self.buffer.extend_from_slice(chunk);
+        // slicing checks the bounds and the character boundaries
+        #[cfg(not(feature = "no-panic"))]
+        let len = self.as_valid_str()[start..end].len();
 
-                    self.sl = nsl as u8;
+        #[cfg(feature = "no-panic")]
+        let len = match self.as_valid_str().get(start..end) {
+            Some(s) => s.len(),
+            None => return Err(Utf8Error::Invalid),
+        };
 
-                    return Ok(());
-                },
-                Ordering::Equal => {
-                    self.buffer.extend_from_slice(chunk);
+        self.poison_with(|builder| {
+            builder.check_limit(len)?;
+
+            let begin = builder.valid_len();
+
+            builder.grow(len)?;
 
-                    self.sl = 0;
-                    // self.sel = 0; // no need
+            // the buffer is thawed by `grow`
+            if builder.binary_sample.inspect(
+                builder.binary_detection,
+                builder.state,
+                &builder.buffer[start..end],
+            ) {
+                return builder.poison(Utf8Error::LooksBinary, &[]);
+            }
+            builder.buffer.extend_from_within(start..end);
+
+            builder.check_profile(begin)
+        })
+    }
+
+    /// Pushes a chunk. Returns whether the data end on a character boundary afterwards. If the chunk is invalid, the bytes before the first invalid character are still pushed.
+    #[inline]
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<PushOutcome, Utf8Error> {
+        self.push_chunk_split(chunk)?;
+
+        Ok(self.state.outcome())
+    }
 
-                    return Ok(());
+    /// Pushes a chunk and returns the number of characters completed by it, including the incomplete character from the previous pushes.
+    pub fn push_chunk_counted(&mut self, chunk: &[u8]) -> Result<usize, Utf8Error> {
+        let needed = self.state.needed();
+
+        let split = self.push_chunk_split(chunk)?;
+
+        // continuation bytes are not counted, so the character completing the previous incomplete one is added separately
+        let mut count = chunk[..split.complete].iter().filter(|&&b| (b as i8) >= -0x40).count();
+
+        if needed > 0 && split.complete > 0 {
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Pushes as much of a chunk as fits under the limit, ending on a character boundary, and ignores the rest. Returns the number of the pushed bytes. Invalid data within the pushed part still fail.
+    #[inline]
+    pub fn push_chunk_saturating(&mut self, chunk: &[u8]) -> Result<usize, Utf8Error> {
+        self.push_chunk_at_most(chunk, usize::MAX)
+    }
+
+    /// Pushes at most `n` bytes of a chunk (and no more than the limit allows), ending on a character boundary if the chunk is cut, and ignores the rest. Returns the number of the pushed bytes, e.g. to be charged to a token bucket. Invalid data within the pushed part still fail.
+    pub fn push_chunk_at_most(&mut self, chunk: &[u8], n: usize) -> Result<usize, Utf8Error> {
+        let room = match self.limit {
+            Some(limit) => limit.saturating_sub(self.len()).min(n),
+            None => n,
+        };
+
+        if chunk.len() <= room {
+            self.push_chunk(chunk)?;
+
+            return Ok(chunk.len());
+        }
+
+        let mut state = self.state;
+
+        let keep = match state::validate(&mut state, &chunk[..room]) {
+            Ok(split) => split.complete,
+            // let the push report the invalid data
+            Err(_) => room,
+        };
+
+        self.push_chunk(&chunk[..keep])?;
+
+        Ok(keep)
+    }
+
+    /// Pushes at most `*remaining` bytes of a chunk for a length-prefixed frame, and subtracts the number of the pushed bytes from `*remaining`. Returns the number of the pushed bytes, so the rest of the chunk belongs to the next frame. If the frame ends in the middle of a character, the push fails with `Utf8Error::Invalid`.
+    pub fn push_exact(&mut self, chunk: &[u8], remaining: &mut usize) -> Result<usize, Utf8Error> {
+        let n = chunk.len().min(*remaining);
+
+        self.push_chunk(&chunk[..n])?;
+
+        *remaining -= n;
+
+        if *remaining == 0 && !self.state.is_valid() {
+            return self.poison(Utf8Error::Invalid, &[]);
+        }
+
+        Ok(n)
+    }
+
+    fn push_chunk_split(&mut self, chunk: &[u8]) -> Result<ValidSplit, Utf8Error> {
+        self.poison_with(|builder| {
+            builder.check_limit(chunk.len())?;
+            builder.check_binary(chunk)?;
+
+            let start = builder.valid_len();
+
+            // the room is made before the state is changed
+            builder.grow(chunk.len())?;
+
+            match builder.validate(chunk) {
+                Ok(split) => {
+                    builder.buffer.extend_from_slice(chunk);
+
+                    builder.check_profile(start)?;
+
+                    Ok(split)
                 },
-                Ordering::Less => {
-                    self.buffer.extend_from_slice(&chunk[..r]);
+                Err(valid_up_to) => {
+                    builder.buffer.extend_from_slice(&chunk[..valid_up_to]);
 
-                    self.sl = 0;
-                    // self.sel = 0; // no need
+                    builder.check_profile(start)?;
 
-                    r
+                    builder.poison(Utf8Error::Invalid, &chunk[valid_up_to..])
                 },
             }
+        })
+    }
+
+    /// Runs a push unless the builder is poisoned, and poisons the builder if the push rejects data.
+    #[inline]
+    fn poison_with<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, Utf8Error>,
+    ) -> Result<T, Utf8Error> {
+        if let Some(err) = self.error.as_ref() {
+            return Err(err.kind);
+        }
+
+        let sample = self.binary_sample;
+
+        // nothing is pushed when a limit is exceeded or an allocation fails, so a smaller push can still follow
+        let result = f(self).or_else(|kind| match kind {
+            Utf8Error::LimitExceeded | Utf8Error::AllocationFailed => {
+                self.binary_sample = sample;
+
+                Err(kind)
+            },
+            _ => self.poison(kind, &[]),
+        });
+
+        #[cfg(all(feature = "diff-test", debug_assertions))]
+        self.cross_check();
+
+        result
+    }
+
+    /// Poisons the builder unless it has been poisoned. `rejected` is the data from the offending position on which are not in the builder.
+    #[inline]
+    fn poison<T>(&mut self, kind: Utf8Error, rejected: &[u8]) -> Result<T, Utf8Error> {
+        if self.error.is_none() {
+            self.error = Some(self.push_error(kind, self.valid_len(), rejected));
+        }
+
+        Err(kind)
+    }
+
+    fn push_error(&self, kind: Utf8Error, offset: usize, rejected: &[u8]) -> PushError {
+        let context = if self.context_len > 0 {
+            let bytes = self.bytes();
+
+            let before = bytes[offset.saturating_sub(self.context_len)..offset].to_vec();
+
+            let after = bytes[offset..]
+                .iter()
+                .chain(rejected.iter())
+                .copied()
+                .take(self.context_len)
+                .collect();
+
+            Some(ErrorContext {
+                before,
+                after,
+            })
         } else {
-            0usize
+            None
         };
 
-        loop {
-            let w = utf8_width::get_width(chunk[e]);
+        PushError {
+            kind,
+            offset,
+            context,
+        }
+    }
 
-            if w == 0 {
-                return Err(Utf8Error);
-            }
+    /// Returns the error of the failed push, or `Utf8Error::Incomplete` if the data end with an incomplete character.
+    #[inline]
+    pub(crate) fn check_finalize(&self) -> Result<(), Utf8Error> {
+        match self.error.as_ref() {
+            Some(err) => Err(err.kind),
+            None if self.is_valid() => Ok(()),
+            None => Err(Utf8Error::Incomplete {
+                have: self.state.pending().len() as u8,
+                need: self.state.needed() as u8,
+            }),
+        }
+    }
 
-            let r = chunk_size - e;
+    /// Validates a chunk with the state, recording the paths into the validation profile if it is enabled.
+    #[inline]
+    fn validate(&mut self, chunk: &[u8]) -> Result<ValidSplit, usize> {
+        #[cfg(feature = "validation-profile")]
+        if let Some(profile) = self.validation_profile.as_mut() {
+            return profile.validate(&mut self.state, chunk);
+        }
 
-            if r >= w {
-                self.buffer.extend_from_slice(&chunk[e..e + w]);
+        state::validate(&mut self.state, chunk)
+    }
 
-                e += w;
+    /// Validates a byte with the state, like `validate`. The state is not changed if the byte is invalid.
+    #[inline]
+    fn validate_byte(&mut self, b: u8) -> Result<(), Utf8Error> {
+        #[cfg(feature = "validation-profile")]
+        if self.validation_profile.is_some() {
+            return self.validate(&[b]).map(|_| ()).map_err(|_| Utf8Error::Invalid);
+        }
 
-                if e == chunk_size {
-                    break;
-                }
-            } else {
-                self.buffer.extend_from_slice(&chunk[e..]);
+        self.state.push(b)
+    }
+
+    #[inline]
+    pub(crate) fn check_limit(&self, additional: usize) -> Result<(), Utf8Error> {
+        match self.limit {
+            Some(limit) if additional > limit.saturating_sub(self.len()) => {
+                Err(Utf8Error::LimitExceeded)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the complete characters after `start` against the profile and the BOM policy. The first rejected character and the data after it are removed.
+    fn check_profile(&mut self, start: usize) -> Result<(), Utf8Error> {
+        let profile = self.profile;
+        let reject_bom = match self.bom_policy {
+            BomPolicy::Keep => false,
+            BomPolicy::Strip => {
+                self.strip_boms(start);
+
+                false
+            },
+            BomPolicy::Reject => true,
+        };
+
+        if profile == Profile::Freeform && !reject_bom {
+            return Ok(());
+        }
+
+        let rejected = self.as_valid_str()[start..]
+            .char_indices()
+            .find(|&(i, c)| {
+                !profile.accepts(c, start + i == 0)
+                    || (reject_bom && c == '\u{FEFF}' && start + i > 0)
+            })
+            .map(|(i, _)| start + i);
+
+        match rejected {
+            Some(end) => {
+                // the rejected characters are captured before being removed
+                self.error = Some(self.push_error(Utf8Error::Invalid, end, &[]));
+
+                self.thaw();
+
+                self.buffer.truncate(end);
+                self.state = Utf8State::new();
+
+                Err(Utf8Error::Invalid)
+            },
+            None => Ok(()),
+        }
+    }
+}
+
+impl Utf8Builder {
+    /// Removes the BOMs which are not at the beginning from the complete characters after `start`.
+    fn strip_boms(&mut self, start: usize) {
+        const BOM: &[u8] = "\u{FEFF}".as_bytes();
+
+        let end = self.valid_len();
 
-                self.sl = r as u8;
-                self.sel = w as u8;
+        if !self.as_valid_str()[start..].contains('\u{FEFF}') {
+            return;
+        }
+
+        self.thaw();
+
+        let mut w = start;
+        let mut r = start;
+
+        while r < end {
+            if r > 0 && self.buffer[r..end].starts_with(BOM) {
+                r += BOM.len();
+            } else {
+                self.buffer[w] = self.buffer[r];
 
-                break;
+                w += 1;
+                r += 1;
             }
         }
 
-        Ok(())
+        self.buffer.drain(w..end);
     }
 }
 
 impl From<&str> for Utf8Builder {
     #[inline]
     fn from(s: &str) -> Self {
-        Utf8Builder {
-            buffer: s.as_bytes().to_vec(), sl: 0, sel: 0
-        }
+        Self::from_buffer(s.as_bytes().to_vec())
     }
 }
 
 impl From<String> for Utf8Builder {
     #[inline]
     fn from(s: String) -> Self {
-        Utf8Builder {
-            buffer: s.into_bytes(), sl: 0, sel: 0
+        Self::from_buffer(s.into_bytes())
+    }
+}
+
+impl TryFrom<Utf8Builder> for String {
+    type Error = Utf8Error;
+
+    /// Try to get the `String` instance, like `Utf8Builder::finalize`.
+    #[inline]
+    fn try_from(builder: Utf8Builder) -> Result<Self, Utf8Error> {
+        builder.finalize()
+    }
+}
+
+/// Slices the complete characters like `String`. It panics if the range is out of the complete characters or not on character boundaries. Use `get` for the checked indexing.
+impl<I: SliceIndex<str>> Index<I> for Utf8Builder {
+    type Output = I::Output;
+
+    #[inline]
+    fn index(&self, index: I) -> &I::Output {
+        &self.as_valid_str()[index]
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for Utf8Builder {
+    /// Wipes the buffer (including its spare capacity) and the incomplete character, leaving the builder empty.
+    #[inline]
+    fn zeroize(&mut self) {
+        // the shared buffer is wiped by its last owner
+        #[cfg(feature = "std")]
+        {
+            self.frozen = None;
         }
+
+        self.buffer.zeroize();
+        self.state.zeroize();
     }
 }
+
+#[cfg(feature = "zeroize")]
+impl Drop for Utf8Builder {
+    #[inline]
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for Utf8Builder {}
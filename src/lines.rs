@@ -0,0 +1,23 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{Utf8Builder, Utf8Error};
+
+impl Utf8Builder {
+    /// Try to get the data split into lines at `\n` or `\r\n`, without producing the whole `String` first. If `keep_terminators` is `true`, the line terminators are kept at the ends of the lines. Like `str::lines`, a trailing empty line is not included.
+    pub fn finalize_lines(self, keep_terminators: bool) -> Result<Vec<String>, Utf8Error> {
+        self.check_finalize()?;
+
+        let s = self.as_valid_str();
+
+        let lines = if keep_terminators {
+            s.split_inclusive('\n').map(ToString::to_string).collect()
+        } else {
+            s.lines().map(ToString::to_string).collect()
+        };
+
+        Ok(lines)
+    }
+}
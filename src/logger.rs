@@ -0,0 +1,144 @@
+use alloc::{format, string::String};
+use core::{
+    cell::UnsafeCell,
+    hint,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use log::{Log, Metadata, Record};
+
+use crate::Utf8Builder;
+
+/// A `log::Log` which captures the records into an in-memory `Utf8Builder` with a size cap, one line per record. When the cap would be exceeded, the oldest lines are dropped. It works without `std`, so it can be put in a `static` and dumped on a fault.
+///
+/// ```rust
+/// use utf8_builder::Utf8Logger;
+///
+/// static LOGGER: Utf8Logger = Utf8Logger::new(4096);
+///
+/// log::set_logger(&LOGGER).unwrap();
+/// log::set_max_level(log::LevelFilter::Info);
+///
+/// log::info!(target: "app", "started");
+///
+/// assert_eq!("INFO app: started\n", LOGGER.dump());
+/// ```
+#[derive(Debug)]
+pub struct Utf8Logger {
+    /// a spin lock, because `std::sync::Mutex` is not available without `std`
+    locked:   AtomicBool,
+    builder:  UnsafeCell<Utf8Builder>,
+    capacity: usize,
+}
+
+// the builder is only accessed while the lock is held
+unsafe impl Sync for Utf8Logger {}
+
+impl Utf8Logger {
+    /// Constructs a new, empty `Utf8Logger` which holds at most `capacity` bytes.
+    #[inline]
+    pub const fn new(capacity: usize) -> Self {
+        Utf8Logger {
+            locked: AtomicBool::new(false),
+            builder: UnsafeCell::new(Utf8Builder::new()),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of bytes held by the logger.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Copies the captured lines into a `String`.
+    #[inline]
+    pub fn dump(&self) -> String {
+        self.with_builder(|builder| String::from(builder.as_valid_str()))
+    }
+
+    /// Takes out the captured lines as a `String`, leaving the logger empty.
+    #[inline]
+    pub fn take(&self) -> String {
+        self.with_builder(|builder| builder.take_valid())
+    }
+
+    /// Appends a line, dropping the oldest lines to make room for it. A line longer than the capacity is cut.
+    fn write_line(&self, mut line: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if line.len() > self.capacity {
+            let mut end = self.capacity - 1;
+
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            line.truncate(end);
+            line.push('\n');
+        }
+
+        self.with_builder(|builder| {
+            let len = builder.len();
+
+            if len + line.len() > self.capacity {
+                let excess = len + line.len() - self.capacity;
+
+                let s = builder.as_valid_str().as_bytes();
+
+                // the line containing the last byte to be dropped is dropped entirely
+                let end = match s[excess - 1..].iter().position(|&b| b == b'\n') {
+                    Some(i) => excess + i,
+                    None => s.len(),
+                };
+
+                builder.discard_valid_to(end);
+            }
+
+            // the builder holds only complete lines and it is never limited, so pushing a `&str` cannot fail
+            let _ = builder.push_str(&line);
+        })
+    }
+
+    fn with_builder<T>(&self, f: impl FnOnce(&mut Utf8Builder) -> T) -> T {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+
+        // the lock is released even if `f` panics
+        let _guard = LockGuard(&self.locked);
+
+        f(unsafe { &mut *self.builder.get() })
+    }
+}
+
+/// Releases the spin lock of a `Utf8Logger` when dropped.
+struct LockGuard<'a>(&'a AtomicBool);
+
+impl Drop for LockGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl Log for Utf8Logger {
+    #[inline]
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    #[inline]
+    fn log(&self, record: &Record) {
+        self.write_line(format!("{} {}: {}\n", record.level(), record.target(), record.args()));
+    }
+
+    #[inline]
+    fn flush(&self) {}
+}
@@ -0,0 +1,188 @@
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// The invalid sequences diverted by `Utf8Builder::push_chunk_diverted`, so that they can be analyzed later.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diverted {
+    /// The raw bytes of the invalid sequences, concatenated.
+    pub bytes: Vec<u8>,
+    /// The position in the builder where the invalid sequences were removed, and the range of them in `bytes`. Adjacent invalid sequences are merged.
+    pub spans: Vec<(usize, Range<usize>)>,
+}
+
+impl Diverted {
+    /// Constructs a new, empty `Diverted`.
+    #[inline]
+    pub const fn new() -> Self {
+        Diverted {
+            bytes: Vec::new(), spans: Vec::new()
+        }
+    }
+
+    /// Records an invalid sequence removed at `offset`.
+    fn record(&mut self, offset: usize, invalid: &[u8]) {
+        let start = self.bytes.len();
+
+        self.bytes.extend_from_slice(invalid);
+
+        match self.spans.last_mut() {
+            Some((last, range)) if *last == offset && range.end == start => {
+                range.end = self.bytes.len()
+            },
+            _ => self.spans.push((offset, start..self.bytes.len())),
+        }
+    }
+}
+
+impl Utf8Builder {
+    /// Gets the `String` instance whether the data are valid or not. If a push has failed or the data end with an incomplete character, a U+FFFD is appended to the complete characters in place of the rejected data and the incomplete character, so it can be used as the fallback of a failed `finalize` without keeping another copy of the input.
+    pub fn into_lossy_string(mut self) -> String {
+        let len = self.valid_len();
+        let lossy = self.error.is_some() || len < self.len();
+
+        self.thaw();
+
+        let mut buffer = core::mem::take(&mut self.buffer);
+
+        if lossy {
+            buffer.truncate(len);
+            buffer.extend_from_slice("\u{FFFD}".as_bytes());
+        }
+
+        unsafe { String::from_utf8_unchecked(buffer) }
+    }
+
+    /// Pushes a chunk with every invalid sequence replaced with U+FFFD, like `String::from_utf8_lossy` but without an intermediate allocation. An incomplete character at the end of the chunk is kept to be completed by the next push.
+    ///
+    /// If the limit would be exceeded or the buffer cannot grow, nothing is pushed.
+    #[inline]
+    pub fn push_str_lossy_from_bytes(&mut self, bytes: &[u8]) -> Result<(), Utf8Error> {
+        self.push_replacing(bytes, |buffer, _| buffer.extend_from_slice("\u{FFFD}".as_bytes()))
+    }
+
+    /// Pushes a chunk with every invalid sequence removed and recorded into `diverted`, so that the readable text can be salvaged while the garbage is preserved. An incomplete character at the end of the chunk is kept to be completed by the next push.
+    ///
+    /// If the limit would be exceeded or the buffer cannot grow, nothing is pushed or recorded.
+    pub fn push_chunk_diverted(
+        &mut self,
+        chunk: &[u8],
+        diverted: &mut Diverted,
+    ) -> Result<(), Utf8Error> {
+        let (bytes_len, spans_len) = (diverted.bytes.len(), diverted.spans.len());
+
+        let result =
+            self.push_replacing(chunk, |buffer, invalid| diverted.record(buffer.len(), invalid));
+
+        if result.is_err() {
+            diverted.bytes.truncate(bytes_len);
+            diverted.spans.truncate(spans_len);
+        }
+
+        result
+    }
+
+    /// Pushes a chunk with every invalid sequence handed to `replace`, which may write a replacement into the buffer.
+    pub(crate) fn push_replacing(
+        &mut self,
+        bytes: &[u8],
+        mut replace: impl FnMut(&mut Vec<u8>, &[u8]),
+    ) -> Result<(), Utf8Error> {
+        self.poison_with(|builder| {
+            builder.check_binary(bytes)?;
+
+            let start = builder.valid_len();
+            let origin = builder.state;
+
+            builder.thaw();
+
+            let mut rest = bytes;
+            let mut result = Ok(());
+
+            loop {
+                let pending = builder.state.pending().len();
+
+                match builder.validate(rest) {
+                    Ok(_) => {
+                        // the limit is checked before growing, so that a huge chunk is not allocated past it
+                        if let Err(err) =
+                            builder.check_limit(rest.len()).and_then(|_| builder.grow(rest.len()))
+                        {
+                            result = Err(err);
+
+                            break;
+                        }
+
+                        builder.buffer.extend_from_slice(rest);
+
+                        break;
+                    },
+                    Err(valid_up_to) => {
+                        if let Err(err) = builder
+                            .check_limit(valid_up_to)
+                            .and_then(|_| builder.grow(valid_up_to + 3))
+                        {
+                            result = Err(err);
+
+                            break;
+                        }
+
+                        builder.buffer.extend_from_slice(&rest[..valid_up_to]);
+
+                        let skip = if valid_up_to == 0 && pending > 0 {
+                            // the incomplete character and its continuation bytes in this chunk are replaced, and the byte breaking it is processed again
+                            let mut state = builder.state;
+
+                            let accepted =
+                                rest.iter().take_while(|&&b| state.push(b).is_ok()).count();
+
+                            let len = builder.buffer.len();
+
+                            // an incomplete character has less than 4 bytes
+                            let mut invalid = [0; 4];
+
+                            invalid[..pending].copy_from_slice(&builder.buffer[len - pending..]);
+                            invalid[pending..pending + accepted].copy_from_slice(&rest[..accepted]);
+
+                            builder.buffer.truncate(len - pending);
+                            builder.state = Default::default();
+
+                            replace(&mut builder.buffer, &invalid[..pending + accepted]);
+
+                            accepted
+                        } else {
+                            let skip = core::str::from_utf8(&rest[valid_up_to..])
+                                .err()
+                                .and_then(|err| err.error_len())
+                                .unwrap_or(rest.len() - valid_up_to);
+
+                            replace(&mut builder.buffer, &rest[valid_up_to..valid_up_to + skip]);
+
+                            skip
+                        };
+
+                        rest = &rest[valid_up_to + skip..];
+
+                        // a replacement is written beyond the checked room, so it is checked right after
+                        if matches!(builder.limit, Some(limit) if builder.buffer.len() > limit) {
+                            result = Err(Utf8Error::LimitExceeded);
+
+                            break;
+                        }
+                    },
+                }
+            }
+
+            if let Err(err) = result {
+                builder.buffer.truncate(start);
+                builder.buffer.extend_from_slice(origin.pending());
+                builder.state = origin;
+
+                return Err(err);
+            }
+
+            builder.check_profile(start)
+        })
+    }
+}
@@ -0,0 +1,100 @@
+use alloc::{string::String, vec::Vec};
+use core::convert::Infallible;
+
+use crate::TeeSink;
+
+/// A counter of the non-overlapping occurrences of a literal pattern, like `str::matches(pattern).count()`. It can be used as the sink of `Utf8Builder::tee` to count the occurrences as the characters complete, including the ones spanning chunk boundaries, without keeping the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchCounter {
+    pattern: String,
+    /// the KMP failure table of the pattern
+    table:   Vec<usize>,
+    /// the number of the matched bytes of the pattern at the end of the recorded text
+    matched: usize,
+    count:   u64,
+}
+
+impl MatchCounter {
+    /// Constructs a new `MatchCounter` for a pattern. An empty pattern never matches.
+    #[inline]
+    pub fn new<S: Into<String>>(pattern: S) -> Self {
+        let pattern = pattern.into();
+        let table = failure_table(pattern.as_bytes());
+
+        MatchCounter {
+            pattern,
+            table,
+            matched: 0,
+            count: 0,
+        }
+    }
+
+    /// Returns the pattern.
+    #[inline]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Records the characters of a `&str`.
+    pub fn record(&mut self, s: &str) {
+        let pattern = self.pattern.as_bytes();
+
+        if pattern.is_empty() {
+            return;
+        }
+
+        for &b in s.as_bytes() {
+            while self.matched > 0 && pattern[self.matched] != b {
+                self.matched = self.table[self.matched - 1];
+            }
+
+            if pattern[self.matched] == b {
+                self.matched += 1;
+            }
+
+            if self.matched == pattern.len() {
+                // the occurrences do not overlap
+                self.matched = 0;
+                self.count += 1;
+            }
+        }
+    }
+
+    /// Returns the number of the occurrences recorded so far.
+    #[inline]
+    pub fn count_matches(&self) -> u64 {
+        self.count
+    }
+}
+
+impl TeeSink for MatchCounter {
+    type Error = Infallible;
+
+    #[inline]
+    fn write_text(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.record(s);
+
+        Ok(())
+    }
+}
+
+/// Computes the KMP failure table of `pattern`, in which `table[i]` is the length of the longest proper prefix of `&pattern[..=i]` which is also its suffix.
+pub(crate) fn failure_table(pattern: &[u8]) -> Vec<usize> {
+    let mut table = alloc::vec![0; pattern.len()];
+
+    let mut k = 0;
+
+    for i in 1..pattern.len() {
+        while k > 0 && pattern[k] != pattern[i] {
+            k = table[k - 1];
+        }
+
+        if pattern[k] == pattern[i] {
+            k += 1;
+        }
+
+        table[i] = k;
+    }
+
+    table
+}
@@ -0,0 +1,94 @@
+use alloc::string::String;
+use core::iter::{FromIterator, Sum};
+
+use crate::{Utf8Builder, Utf8Error};
+
+impl Utf8Builder {
+    /// Appends the data of another builder, including its incomplete character, as if they were pushed as a chunk. If this builder is empty, the buffer of `other` is taken without copying.
+    ///
+    /// The data rejected by `other` are not appended, and if `other` is poisoned, this builder is poisoned as well.
+    pub fn append(&mut self, mut other: Utf8Builder) -> Result<(), Utf8Error> {
+        if self.is_empty() && self.error.is_none() {
+            self.poison_with(|builder| {
+                builder.check_limit(other.len())?;
+                builder.check_binary(other.bytes())?;
+
+                other.thaw();
+
+                builder.replace_buffer(core::mem::take(&mut other.buffer));
+                builder.state = other.state;
+
+                builder.check_profile(0)
+            })?;
+        } else {
+            self.push_chunk(other.bytes())?;
+        }
+
+        match other.error.as_ref() {
+            Some(err) => self.poison(err.kind, &[]),
+            None => Ok(()),
+        }
+    }
+
+    /// Merges the lines of this builder and `other`, which are both sorted, into a new builder, like the merge step of an external sort. Every line of the result ends with `\n`. The lines are split at `\n` or `\r\n` and compared as strings, and the lines of this builder come first among equal lines.
+    ///
+    /// Both builders must be complete, and the merged data are not validated again.
+    pub fn merge_sorted_lines(&self, other: &Utf8Builder) -> Result<Utf8Builder, Utf8Error> {
+        self.check_finalize()?;
+        other.check_finalize()?;
+
+        let mut merged = String::with_capacity(self.len() + other.len() + 2);
+
+        let mut a = self.as_valid_str().lines().peekable();
+        let mut b = other.as_valid_str().lines().peekable();
+
+        loop {
+            let line = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if y < x => b.next(),
+                (Some(_), _) => a.next(),
+                (None, _) => b.next(),
+            };
+
+            match line {
+                Some(line) => {
+                    merged.push_str(line);
+                    merged.push('\n');
+                },
+                None => break,
+            }
+        }
+
+        Ok(Utf8Builder::from(merged))
+    }
+}
+
+impl Extend<Utf8Builder> for Utf8Builder {
+    /// Appends builders one by one with `append`. A failure poisons the builder, so it is reported by `finalize`.
+    #[inline]
+    fn extend<I: IntoIterator<Item = Utf8Builder>>(&mut self, iter: I) {
+        for builder in iter {
+            if let Err(err) = self.append(builder) {
+                // exceeding the limit or failing to allocate does not poison the builder, so remember every failure this way
+                let _ = self.poison::<()>(err, &[]);
+            }
+        }
+    }
+}
+
+impl FromIterator<Utf8Builder> for Utf8Builder {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Utf8Builder>>(iter: I) -> Self {
+        let mut builder = Utf8Builder::new();
+
+        builder.extend(iter);
+
+        builder
+    }
+}
+
+impl Sum<Utf8Builder> for Utf8Builder {
+    #[inline]
+    fn sum<I: Iterator<Item = Utf8Builder>>(iter: I) -> Self {
+        iter.collect()
+    }
+}
@@ -0,0 +1,199 @@
+use alloc::string::String;
+use std::time::{Duration, Instant};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// The accounting of a push, given to a `PushObserver`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PushStats {
+    /// The number of bytes accepted by this push.
+    pub bytes:       usize,
+    /// The number of characters completed by this push.
+    pub chars:       usize,
+    /// The number of bytes accepted so far.
+    pub total_bytes: u64,
+    /// The number of characters completed so far.
+    pub total_chars: u64,
+    /// The time elapsed since the `ObservedBuilder` was created.
+    pub elapsed:     Duration,
+}
+
+/// An observer which is invoked on every push of an `ObservedBuilder`. Returning an error makes the builder fail permanently.
+pub trait PushObserver {
+    /// Observes a successful push.
+    fn on_push(&mut self, stats: &PushStats) -> Result<(), Utf8Error>;
+}
+
+impl<F: FnMut(&PushStats) -> Result<(), Utf8Error>> PushObserver for F {
+    #[inline]
+    fn on_push(&mut self, stats: &PushStats) -> Result<(), Utf8Error> {
+        self(stats)
+    }
+}
+
+/// A built-in `PushObserver` which enforces a minimum throughput and a maximum duration, to defend against slow senders (e.g. slowloris).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThroughputPolicy {
+    min_bytes_per_sec: u64,
+    grace:             Duration,
+    max_duration:      Option<Duration>,
+}
+
+impl ThroughputPolicy {
+    /// Constructs a new `ThroughputPolicy` which requires at least `min_bytes_per_sec` bytes per second on average, checked after the `grace` period.
+    #[inline]
+    pub const fn new(min_bytes_per_sec: u64, grace: Duration) -> Self {
+        ThroughputPolicy {
+            min_bytes_per_sec,
+            grace,
+            max_duration: None,
+        }
+    }
+
+    /// Sets the maximum duration since the builder was created.
+    #[inline]
+    pub const fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+
+        self
+    }
+}
+
+impl PushObserver for ThroughputPolicy {
+    fn on_push(&mut self, stats: &PushStats) -> Result<(), Utf8Error> {
+        if let Some(max_duration) = self.max_duration {
+            if stats.elapsed > max_duration {
+                return Err(Utf8Error::PolicyViolated);
+            }
+        }
+
+        if stats.elapsed > self.grace {
+            let expected =
+                stats.elapsed.as_millis().saturating_mul(self.min_bytes_per_sec as u128) / 1000;
+
+            if (stats.total_bytes as u128) < expected {
+                return Err(Utf8Error::PolicyViolated);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A `Utf8Builder` which reports every push to a `PushObserver`. Created by `Utf8Builder::observe`.
+#[derive(Debug, Clone)]
+pub struct ObservedBuilder<O> {
+    builder:     Utf8Builder,
+    observer:    O,
+    created:     Instant,
+    total_bytes: u64,
+    total_chars: u64,
+}
+
+impl Utf8Builder {
+    /// Wraps this builder so that every push from now on is reported to `observer`.
+    #[inline]
+    pub fn observe<O: PushObserver>(self, observer: O) -> ObservedBuilder<O> {
+        ObservedBuilder {
+            builder: self,
+            observer,
+            created: Instant::now(),
+            total_bytes: 0,
+            total_chars: 0,
+        }
+    }
+}
+
+impl<O: PushObserver> ObservedBuilder<O> {
+    /// Gets a reference to the underlying builder.
+    #[inline]
+    pub fn builder(&self) -> &Utf8Builder {
+        &self.builder
+    }
+
+    /// Gets a reference to the observer.
+    #[inline]
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Pushes a byte.
+    #[inline]
+    pub fn push(&mut self, b: u8) -> Result<(), Utf8Error> {
+        self.observe_with(|builder| {
+            builder.push(b)?;
+
+            Ok((1, builder.is_valid() as usize))
+        })
+    }
+
+    /// Pushes a `&str`.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), Utf8Error> {
+        self.observe_with(|builder| {
+            builder.push_str(s)?;
+
+            Ok((s.len(), s.chars().count()))
+        })
+    }
+
+    /// Pushes a char.
+    #[inline]
+    pub fn push_char(&mut self, c: char) -> Result<(), Utf8Error> {
+        self.observe_with(|builder| {
+            builder.push_char(c)?;
+
+            Ok((c.len_utf8(), 1))
+        })
+    }
+
+    /// Pushes a chunk.
+    #[inline]
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<(), Utf8Error> {
+        self.observe_with(|builder| Ok((chunk.len(), builder.push_chunk_counted(chunk)?)))
+    }
+
+    /// Try to get the `String` instance. It fails if the observer has rejected a push.
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        self.builder.finalize()
+    }
+
+    /// Consumes this `ObservedBuilder`, returning the builder and the observer. The data of a push rejected by the observer are not in the builder, which is poisoned with the error of the observer.
+    #[inline]
+    pub fn into_parts(self) -> (Utf8Builder, O) {
+        (self.builder, self.observer)
+    }
+
+    fn observe_with(
+        &mut self,
+        f: impl FnOnce(&mut Utf8Builder) -> Result<(usize, usize), Utf8Error>,
+    ) -> Result<(), Utf8Error> {
+        let len = self.builder.len();
+        let state = self.builder.state;
+
+        let (bytes, chars) = f(&mut self.builder)?;
+
+        let stats = PushStats {
+            bytes,
+            chars,
+            total_bytes: self.total_bytes + bytes as u64,
+            total_chars: self.total_chars + chars as u64,
+            elapsed: self.created.elapsed(),
+        };
+
+        if let Err(err) = self.observer.on_push(&stats) {
+            // the rejected push is taken back before the builder is poisoned
+            self.builder.thaw();
+            self.builder.buffer.truncate(len);
+            self.builder.state = state;
+
+            return self.builder.poison(err, &[]);
+        }
+
+        self.total_bytes = stats.total_bytes;
+        self.total_chars = stats.total_chars;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::{error::Error, fmt};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// Errors which can occur when finalizing a `Utf8Builder` into a parsed value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError<E> {
+    /// The data are not valid UTF-8.
+    Utf8(Utf8Error),
+    /// The text cannot be parsed.
+    Parse(E),
+}
+
+impl<E> From<Utf8Error> for ParseError<E> {
+    #[inline]
+    fn from(error: Utf8Error) -> Self {
+        ParseError::Utf8(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Display> fmt::Display for ParseError<E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Utf8(err) => fmt::Display::fmt(err, f),
+            ParseError::Parse(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Error> Error for ParseError<E> {}
+
+impl Utf8Builder {
+    /// Checks the data are complete and parses them with `FromStr`, without producing a `String`.
+    #[inline]
+    pub fn finalize_parse<T: FromStr>(self) -> Result<T, ParseError<T::Err>> {
+        self.check_finalize()?;
+
+        self.as_valid_str().parse().map_err(ParseError::Parse)
+    }
+}
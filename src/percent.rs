@@ -0,0 +1,112 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::{TeeSink, Utf8Builder, Utf8Error};
+
+/// A set of ASCII characters to be percent-encoded. The non-ASCII bytes are always percent-encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AsciiSet {
+    mask: u128,
+}
+
+impl AsciiSet {
+    /// The C0 control characters and DEL.
+    pub const CONTROLS: AsciiSet = AsciiSet {
+        mask: 0xFFFF_FFFF | 1 << 0x7F
+    };
+    /// An empty set.
+    pub const EMPTY: AsciiSet = AsciiSet {
+        mask: 0
+    };
+    /// All ASCII characters except the ASCII alphanumeric characters.
+    pub const NON_ALPHANUMERIC: AsciiSet = AsciiSet {
+        mask: !(0x3FF << b'0' | 0x3FF_FFFF << b'A' | 0x3FF_FFFF << b'a'),
+    };
+
+    /// Adds an ASCII character to the set.
+    #[inline]
+    pub const fn add(self, b: u8) -> Self {
+        AsciiSet {
+            mask: self.mask | 1 << (b & 0x7F)
+        }
+    }
+
+    /// Removes an ASCII character from the set.
+    #[inline]
+    pub const fn remove(self, b: u8) -> Self {
+        AsciiSet {
+            mask: self.mask & !(1 << (b & 0x7F))
+        }
+    }
+
+    /// Returns whether a byte is to be percent-encoded.
+    #[inline]
+    pub const fn should_encode(&self, b: u8) -> bool {
+        b >= 0x80 || self.mask & 1 << b != 0
+    }
+}
+
+/// A `TeeSink` which writes the complete characters percent-encoded into a `fmt::Write`.
+#[derive(Debug, Clone)]
+pub struct PercentEncodeSink<W> {
+    /// The underlying writer.
+    pub writer: W,
+    /// The characters to be percent-encoded.
+    pub set:    AsciiSet,
+}
+
+impl<W: Write> TeeSink for PercentEncodeSink<W> {
+    type Error = fmt::Error;
+
+    #[inline]
+    fn write_text(&mut self, s: &str) -> Result<(), Self::Error> {
+        percent_encode(s, &self.set, &mut self.writer)
+    }
+}
+
+impl Utf8Builder {
+    /// Try to get the `String` instance with the bytes in `set` and the non-ASCII bytes percent-encoded.
+    #[inline]
+    pub fn finalize_percent_encoded(self, set: &AsciiSet) -> Result<String, Utf8Error> {
+        self.check_finalize()?;
+
+        let mut s = String::with_capacity(self.len());
+
+        self.write_percent_encoded(set, &mut s).unwrap();
+
+        Ok(s)
+    }
+
+    /// Writes the complete characters with the bytes in `set` and the non-ASCII bytes percent-encoded into a `fmt::Write`.
+    #[inline]
+    pub fn write_percent_encoded<W: Write>(&self, set: &AsciiSet, writer: &mut W) -> fmt::Result {
+        percent_encode(self.as_valid_str(), set, writer)
+    }
+}
+
+fn percent_encode<W: Write>(s: &str, set: &AsciiSet, writer: &mut W) -> fmt::Result {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+    let bytes = s.as_bytes();
+
+    let mut start = 0;
+
+    for (i, b) in bytes.iter().copied().enumerate() {
+        if !set.should_encode(b) {
+            continue;
+        }
+
+        // the bytes in `start..i` are ASCII characters which are not encoded, so it is on character boundaries if it is not empty
+        if start < i {
+            writer.write_str(&s[start..i])?;
+        }
+
+        let buf = [b'%', HEX[(b >> 4) as usize], HEX[(b & 0xF) as usize]];
+
+        writer.write_str(unsafe { core::str::from_utf8_unchecked(&buf) })?;
+
+        start = i + 1;
+    }
+
+    writer.write_str(&s[start..])
+}
@@ -0,0 +1,122 @@
+use alloc::vec;
+use std::io::{ErrorKind, Read, Write};
+
+use crate::{FinalizeError, Utf8Builder};
+
+/// Connects an `io::Read` to an `io::Write` while validating the data as UTF-8, like `iconv -f utf-8 -t utf-8`, e.g. for piping stdin to stdout in a CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Utf8Pipe {
+    /// Whether every invalid sequence is replaced with U+FFFD instead of failing.
+    pub lossy:       bool,
+    /// The number of bytes read at a time.
+    pub buffer_size: usize,
+}
+
+/// The counters of the data written by a `Utf8Pipe`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PipeStats {
+    /// The number of the written bytes.
+    pub bytes:    u64,
+    /// The number of the written characters.
+    pub chars:    u64,
+    /// The number of the written line feeds.
+    pub lines:    u64,
+    /// The number of the invalid sequences replaced with U+FFFD.
+    pub replaced: u64,
+}
+
+impl Utf8Pipe {
+    /// Constructs a new `Utf8Pipe` which reads 8 KiB at a time.
+    #[inline]
+    pub const fn new(lossy: bool) -> Self {
+        Utf8Pipe {
+            lossy,
+            buffer_size: 8 * 1024,
+        }
+    }
+
+    /// Copies all data from `reader` to `writer`, and returns the counters of the written data. Only complete characters are written, so the output ends on a character boundary even if the copy fails.
+    ///
+    /// If the pipe is not lossy, it fails with `FinalizeError::Utf8` at the first invalid sequence, after the data before it have been written.
+    pub fn pipe<R: Read, W: Write>(
+        &self,
+        mut reader: R,
+        mut writer: W,
+    ) -> Result<PipeStats, FinalizeError> {
+        let mut stats = PipeStats::default();
+
+        let mut buf = vec![0; self.buffer_size.max(1)];
+        let mut builder = Utf8Builder::new();
+
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            };
+
+            let result = if self.lossy {
+                let replaced = &mut stats.replaced;
+
+                builder.push_replacing(&buf[..n], |buffer, _| {
+                    buffer.extend_from_slice("\u{FFFD}".as_bytes());
+
+                    *replaced += 1;
+                })
+            } else {
+                builder.push_chunk(&buf[..n]).map(|_| ())
+            };
+
+            // the complete characters before an invalid sequence are written as well
+            Self::drain(&mut builder, &mut writer, &mut stats)?;
+
+            result?;
+        }
+
+        if !builder.is_valid() {
+            if !self.lossy {
+                builder.check_finalize()?;
+            }
+
+            writer.write_all("\u{FFFD}".as_bytes())?;
+
+            stats.bytes += 3;
+            stats.chars += 1;
+            stats.replaced += 1;
+        }
+
+        writer.flush()?;
+
+        Ok(stats)
+    }
+
+    /// Writes out the complete characters of the builder.
+    fn drain<W: Write>(
+        builder: &mut Utf8Builder,
+        writer: &mut W,
+        stats: &mut PipeStats,
+    ) -> Result<(), FinalizeError> {
+        let s = builder.as_valid_str();
+
+        writer.write_all(s.as_bytes())?;
+
+        stats.bytes += s.len() as u64;
+        stats.chars += s.chars().count() as u64;
+        stats.lines += s.bytes().filter(|&b| b == b'\n').count() as u64;
+
+        let len = s.len();
+
+        builder.discard_valid_to(len);
+
+        Ok(())
+    }
+}
+
+impl Default for Utf8Pipe {
+    /// A strict pipe which reads 8 KiB at a time.
+    #[inline]
+    fn default() -> Self {
+        Utf8Pipe::new(false)
+    }
+}
@@ -0,0 +1,97 @@
+use alloc::{string::String, vec::Vec};
+use std::{
+    sync::mpsc::{self, SyncSender, TrySendError},
+    thread::{self, JoinHandle},
+};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// A builder which validates and stores chunks on a dedicated worker thread. Chunks are handed off through a bounded queue, so the thread which pushes chunks does not spend time on validation.
+#[derive(Debug)]
+pub struct PipelinedBuilder {
+    sender: SyncSender<Vec<u8>>,
+    /// the worker thread, which has been joined if it is `Err`
+    handle: Result<JoinHandle<Result<String, Utf8Error>>, Utf8Error>,
+}
+
+impl PipelinedBuilder {
+    /// Constructs a new `PipelinedBuilder` and spawns its worker thread. At most `bound` chunks can be queued.
+    #[inline]
+    pub fn new(bound: usize) -> Self {
+        Self::with_builder(bound, Utf8Builder::new())
+    }
+
+    /// Constructs a new `PipelinedBuilder` which continues building from an existing `Utf8Builder`.
+    pub fn with_builder(bound: usize, mut builder: Utf8Builder) -> Self {
+        let (sender, receiver) = mpsc::sync_channel::<Vec<u8>>(bound);
+
+        let handle = thread::spawn(move || {
+            for chunk in receiver {
+                builder.push_chunk(&chunk)?;
+            }
+
+            builder.finalize()
+        });
+
+        PipelinedBuilder {
+            sender,
+            handle: Ok(handle),
+        }
+    }
+
+    /// Pushes a chunk. It blocks if the queue is full. If the worker thread has already failed, its error is returned.
+    #[inline]
+    pub fn push_chunk<C: Into<Vec<u8>>>(&mut self, chunk: C) -> Result<(), Utf8Error> {
+        match self.sender.send(chunk.into()) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(self.worker_error()),
+        }
+    }
+
+    /// Tries to push a chunk without blocking. If the queue is full, the chunk is given back as `Ok(Some(chunk))`. If the worker thread has already failed, its error is returned.
+    #[inline]
+    pub fn try_push_chunk<C: Into<Vec<u8>>>(
+        &mut self,
+        chunk: C,
+    ) -> Result<Option<Vec<u8>>, Utf8Error> {
+        match self.sender.try_send(chunk.into()) {
+            Ok(()) => Ok(None),
+            Err(TrySendError::Full(chunk)) => Ok(Some(chunk)),
+            Err(TrySendError::Disconnected(_)) => Err(self.worker_error()),
+        }
+    }
+
+    /// Waits for the worker thread to process all queued chunks, and tries to get the `String` instance.
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        drop(self.sender);
+
+        match self.handle {
+            Ok(handle) => join(handle),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Joins the worker thread which has stopped receiving chunks, and returns its error.
+    fn worker_error(&mut self) -> Utf8Error {
+        let handle = core::mem::replace(&mut self.handle, Err(Utf8Error::Invalid));
+
+        let err = match handle {
+            // the worker thread only stops receiving chunks when a push fails
+            Ok(handle) => join(handle).err().unwrap_or(Utf8Error::Invalid),
+            Err(err) => err,
+        };
+
+        self.handle = Err(err);
+
+        err
+    }
+}
+
+#[inline]
+fn join(handle: JoinHandle<Result<String, Utf8Error>>) -> Result<String, Utf8Error> {
+    match handle.join() {
+        Ok(result) => result,
+        Err(err) => std::panic::resume_unwind(err),
+    }
+}
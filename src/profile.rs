@@ -0,0 +1,60 @@
+/// Named policies which restrict the characters accepted by a `Utf8Builder`, beyond being valid UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// Accepts all valid UTF-8 data. This is the default profile.
+    Freeform,
+    /// Rejects noncharacters (U+FDD0..=U+FDEF and U+xxFFFE/U+xxFFFF), BOMs (U+FEFF) which are not at the beginning, and control characters other than TAB, LF and CR.
+    Strict,
+    /// Rejects noncharacters, BOMs, control characters and whitespace characters.
+    Identifier,
+}
+
+impl Profile {
+    /// Returns whether a character is accepted. `at_start` indicates whether the character is at the beginning of the data.
+    #[inline]
+    pub fn accepts(self, c: char, at_start: bool) -> bool {
+        match self {
+            Profile::Freeform => true,
+            Profile::Strict => {
+                !is_noncharacter(c)
+                    && (c != '\u{FEFF}' || at_start)
+                    && (!c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+            },
+            Profile::Identifier => {
+                !is_noncharacter(c) && c != '\u{FEFF}' && !c.is_control() && !c.is_whitespace()
+            },
+        }
+    }
+}
+
+impl Default for Profile {
+    #[inline]
+    fn default() -> Self {
+        Profile::Freeform
+    }
+}
+
+/// Policies for the BOMs (U+FEFF ZERO WIDTH NO-BREAK SPACE) which are not at the beginning of the data, e.g. left by concatenating files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BomPolicy {
+    /// Keeps the BOMs. This is the default policy.
+    Keep,
+    /// Removes the BOMs as they arrive.
+    Strip,
+    /// Rejects the BOMs as invalid data.
+    Reject,
+}
+
+impl Default for BomPolicy {
+    #[inline]
+    fn default() -> Self {
+        BomPolicy::Keep
+    }
+}
+
+#[inline]
+fn is_noncharacter(c: char) -> bool {
+    let u = c as u32;
+
+    (0xFDD0..=0xFDEF).contains(&u) || u & 0xFFFE == 0xFFFE
+}
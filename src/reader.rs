@@ -0,0 +1,133 @@
+use alloc::string::String;
+use std::io::{self, BufRead, Read};
+
+use crate::{matches::failure_table, Utf8Builder};
+
+/// A reader draining the complete characters of a `Utf8Builder` as they are read, created by `Utf8Builder::bytes_reader`. The incomplete character (if any) is left in the builder.
+///
+/// The read data are removed from the builder once at least half of the complete characters have been read, and when the reader is dropped, so reading in small pieces does not move the rest of the data every time.
+#[derive(Debug)]
+pub struct BytesReader<'a> {
+    pub(crate) builder: &'a mut Utf8Builder,
+    /// the number of bytes which have been read but not removed from the builder
+    pub(crate) offset:  usize,
+}
+
+impl Read for BytesReader<'_> {
+    /// Reads the complete characters. `Ok(0)` is returned if there are no more complete characters, so more data can be pushed and read with a new reader.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let s = &self.builder.as_valid_str().as_bytes()[self.offset..];
+
+        let n = s.len().min(buf.len());
+
+        buf[..n].copy_from_slice(&s[..n]);
+
+        self.offset += n;
+
+        if self.offset * 2 >= self.builder.valid_len() {
+            self.compact();
+        }
+
+        Ok(n)
+    }
+}
+
+impl Drop for BytesReader<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.compact();
+    }
+}
+
+impl BytesReader<'_> {
+    /// Removes the characters which have been read from the builder.
+    fn compact(&mut self) {
+        // a character which has been read partially stays in the builder until its last byte is read
+        let end = self.builder.floor_char_boundary(self.offset);
+
+        self.builder.discard_valid_to(end);
+        self.offset -= end;
+    }
+}
+
+impl Utf8Builder {
+    /// Creates a reader which drains the complete characters as they are read, so that the builder can act as a bounded buffer validating data between two I/O endpoints. If the reader is dropped in the middle of a character, the whole character is kept in the builder.
+    #[inline]
+    pub fn bytes_reader(&mut self) -> BytesReader<'_> {
+        BytesReader {
+            builder: self, offset: 0
+        }
+    }
+}
+
+impl Utf8Builder {
+    /// Reads from `reader` and validates the data until `delimiter` or EOF, like `BufRead::read_until`. The reader is left after the delimiter, and the delimiter is included in the `String` if `inclusive` is `true`. If the data are not valid UTF-8, an error of `io::ErrorKind::InvalidData` is returned, and the reader is left after the offending chunk.
+    pub fn from_reader_until<R: BufRead>(
+        reader: &mut R,
+        delimiter: &[u8],
+        inclusive: bool,
+    ) -> io::Result<String> {
+        let table = failure_table(delimiter);
+
+        let mut builder = Utf8Builder::new();
+
+        // the number of the matched bytes of the delimiter, which have not been pushed
+        let mut matched = 0;
+
+        loop {
+            let buf = match reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+
+            // the data are the matched bytes followed by `buf`
+            let carried = matched;
+            let mut end = None;
+
+            if matched == delimiter.len() {
+                end = Some(0);
+            } else {
+                for (i, &b) in buf.iter().enumerate() {
+                    while matched > 0 && delimiter[matched] != b {
+                        matched = table[matched - 1];
+                    }
+
+                    if delimiter[matched] == b {
+                        matched += 1;
+                    }
+
+                    if matched == delimiter.len() {
+                        end = Some(i + 1);
+
+                        break;
+                    }
+                }
+            }
+
+            let (consumed, pushed) = match end {
+                Some(end) if inclusive => (end, carried + end),
+                Some(end) => (end, carried + end - delimiter.len()),
+                // the rest of the data are pushed at EOF
+                None if buf.is_empty() => (0, carried),
+                None => (buf.len(), carried + buf.len() - matched),
+            };
+
+            let result = builder
+                .push_chunk(&delimiter[..pushed.min(carried)])
+                .and_then(|_| builder.push_chunk(&buf[..pushed.saturating_sub(carried)]));
+
+            let eof = buf.is_empty();
+
+            reader.consume(consumed);
+
+            result.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+            if end.is_some() || eof {
+                return builder
+                    .finalize()
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err));
+            }
+        }
+    }
+}
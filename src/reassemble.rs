@@ -0,0 +1,156 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{state, Utf8Builder, Utf8Error, Utf8State};
+
+/// A builder which accepts chunks at absolute offsets out of order (e.g. from UDP or QUIC streams, or parallel range requests), and stitches the characters split across them as the gaps are filled.
+///
+/// The data from offset 0 without gaps are pushed into a `Utf8Builder`. The chunks after a gap are buffered, and their interiors are validated on arrival, so invalid data are rejected before the gaps are filled. Overlapping data are not compared, and the bytes which arrive first win, whatever their offsets are.
+#[derive(Debug, Clone, Default)]
+pub struct Utf8Reassembler {
+    builder:   Utf8Builder,
+    /// the chunks after the first gap, by their offsets
+    fragments: BTreeMap<usize, Vec<u8>>,
+}
+
+impl Utf8Reassembler {
+    /// Constructs a new, empty `Utf8Reassembler`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_builder(Utf8Builder::new())
+    }
+
+    /// Constructs a new `Utf8Reassembler` whose data start after the data of an existing `Utf8Builder`. The limit of the builder also applies to the offsets.
+    #[inline]
+    pub fn with_builder(builder: Utf8Builder) -> Self {
+        Utf8Reassembler {
+            builder,
+            fragments: BTreeMap::new(),
+        }
+    }
+
+    /// Gets a reference to the builder of the contiguous data.
+    #[inline]
+    pub fn builder(&self) -> &Utf8Builder {
+        &self.builder
+    }
+
+    /// Returns the offset of the first gap, or `None` if there is no chunk after a gap.
+    #[inline]
+    pub fn first_gap(&self) -> Option<usize> {
+        if self.fragments.is_empty() {
+            None
+        } else {
+            Some(self.builder.len())
+        }
+    }
+
+    /// Returns whether the data are contiguous and end on a character boundary.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.fragments.is_empty() && self.builder.is_valid()
+    }
+
+    /// Inserts a chunk at an absolute offset.
+    pub fn insert(&mut self, offset: usize, chunk: &[u8]) -> Result<(), Utf8Error> {
+        if let Some(limit) = self.builder.limit() {
+            if offset.saturating_add(chunk.len()) > limit {
+                return Err(Utf8Error::LimitExceeded);
+            }
+        }
+
+        let len = self.builder.len();
+
+        // the bytes before `len` have arrived
+        let (offset, chunk) = if offset < len {
+            (len, &chunk[(len - offset).min(chunk.len())..])
+        } else {
+            (offset, chunk)
+        };
+
+        if chunk.is_empty() {
+            return Ok(());
+        }
+
+        if offset > len && !is_valid_interior(chunk) {
+            return Err(Utf8Error::Invalid);
+        }
+
+        // the fragments never overlap, and only the ranges of the chunk which have not arrived are kept
+        let end = offset + chunk.len();
+
+        let mut pieces = Vec::new();
+        let mut start = offset;
+
+        for (&fragment_offset, fragment) in self.fragments.range(..end) {
+            let fragment_end = fragment_offset + fragment.len();
+
+            if fragment_end <= start {
+                continue;
+            }
+
+            if fragment_offset > start {
+                pieces.push((start, fragment_offset));
+            }
+
+            start = fragment_end;
+
+            if start >= end {
+                break;
+            }
+        }
+
+        if start < end {
+            pieces.push((start, end));
+        }
+
+        for (start, end) in pieces {
+            let piece = &chunk[start - offset..end - offset];
+
+            if start == len {
+                self.builder.push_chunk(piece)?;
+            } else {
+                self.fragments.insert(start, piece.to_vec());
+            }
+        }
+
+        // the chunks after the first gap may have become contiguous
+        while let Some((&offset, _)) = self.fragments.iter().next() {
+            let len = self.builder.len();
+
+            if offset > len {
+                break;
+            }
+
+            let fragment = self.fragments.remove(&offset).unwrap();
+
+            if offset + fragment.len() > len {
+                self.builder.push_chunk(&fragment[len - offset..])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Try to get the `String` instance. It fails with `Utf8Error::Incomplete` if there is a gap, where `have` is the length of the incomplete character before the gap and `need` is the length of the gap (at most 255).
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        if let Some((&offset, _)) = self.fragments.iter().next() {
+            let gap = offset - self.builder.len();
+
+            return Err(Utf8Error::Incomplete {
+                have: self.builder.state().pending().len() as u8,
+                need: gap.min(u8::MAX as usize) as u8,
+            });
+        }
+
+        self.builder.finalize()
+    }
+}
+
+/// Validates a chunk which may start and end in the middle of characters.
+fn is_valid_interior(chunk: &[u8]) -> bool {
+    // a character has at most 3 continuation bytes
+    let start = chunk.iter().take(3).take_while(|&&b| b & 0xC0 == 0x80).count();
+
+    state::validate(&mut Utf8State::new(), &chunk[start..]).is_ok()
+}
@@ -0,0 +1,124 @@
+use core::iter::FusedIterator;
+
+use crate::{state, Utf8Builder};
+
+/// A segment of the rest of a chunk yielded by `ErrorRecovery`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Segment<'a> {
+    /// Complete characters.
+    Valid(&'a str),
+    /// An invalid sequence, which is at most 3 bytes long.
+    Invalid(&'a [u8]),
+    /// The beginning of a valid character at the end of the chunk, which may be completed by the next chunk.
+    Incomplete(&'a [u8]),
+}
+
+/// An iterator over the rest of a chunk which has not been pushed, in alternating segments of valid and invalid data. Created by `Utf8Builder::error_recovery_iterator`.
+#[derive(Debug, Clone)]
+pub struct ErrorRecovery<'a> {
+    rest:     &'a [u8],
+    /// the incomplete character removed from the builder because the chunk cannot complete it
+    orphaned: [u8; 4],
+    /// the length of `orphaned`
+    ol:       u8,
+}
+
+impl Utf8Builder {
+    /// Pushes the longest valid prefix of a chunk without poisoning the builder, and returns an iterator over the rest of the chunk, so that a custom recovery policy can decide what to push for it. If the chunk is valid, the whole chunk is pushed and the iterator is empty.
+    ///
+    /// If the chunk cannot complete the incomplete character of the builder, the character is removed from the builder and can be obtained by `ErrorRecovery::orphaned`. If the prefix cannot be pushed (e.g. the limit would be exceeded), nothing is pushed and the iterator covers the whole chunk.
+    ///
+    /// ```rust
+    /// use utf8_builder::{Segment, Utf8Builder};
+    ///
+    /// let mut builder = Utf8Builder::new();
+    ///
+    /// let recovery = builder.error_recovery_iterator(b"ab\xFFc\xE4");
+    ///
+    /// for segment in recovery {
+    ///     match segment {
+    ///         Segment::Valid(s) => builder.push_str(s).unwrap(),
+    ///         Segment::Invalid(_) => builder.push_char('?').unwrap(),
+    ///         Segment::Incomplete(bytes) => {
+    ///             builder.push_chunk(bytes).map(|_| ()).unwrap()
+    ///         },
+    ///     }
+    /// }
+    ///
+    /// builder.push_chunk(b"\xB8\xAD").unwrap();
+    ///
+    /// assert_eq!("ab?c中", builder.finalize().unwrap());
+    /// ```
+    pub fn error_recovery_iterator<'a>(&mut self, chunk: &'a [u8]) -> ErrorRecovery<'a> {
+        let mut state = self.state;
+
+        let (valid_up_to, failed) = match state::validate(&mut state, chunk) {
+            Ok(_) => (chunk.len(), false),
+            Err(valid_up_to) => (valid_up_to, true),
+        };
+
+        let mut orphaned = [0; 4];
+        let mut ol = 0;
+
+        // only a character which the chunk fails to complete is orphaned
+        if self.error.is_none() && failed && valid_up_to == 0 && !self.is_valid() {
+            ol = self.shrink_pending_into(&mut orphaned);
+        }
+
+        let pushed = match self.push_chunk(&chunk[..valid_up_to]) {
+            Ok(_) => valid_up_to,
+            Err(_) => 0,
+        };
+
+        ErrorRecovery {
+            rest: &chunk[pushed..],
+            orphaned,
+            ol: ol as u8,
+        }
+    }
+}
+
+impl<'a> ErrorRecovery<'a> {
+    /// Returns the incomplete character which was removed from the builder because the chunk cannot complete it.
+    #[inline]
+    pub fn orphaned(&self) -> &[u8] {
+        &self.orphaned[..self.ol as usize]
+    }
+
+    /// Returns the rest of the chunk which has not been yielded.
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.rest
+    }
+}
+
+impl<'a> Iterator for ErrorRecovery<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let (segment, len) = match core::str::from_utf8(self.rest) {
+            Ok(s) => (Segment::Valid(s), s.len()),
+            Err(err) => match err.valid_up_to() {
+                0 => match err.error_len() {
+                    Some(len) => (Segment::Invalid(&self.rest[..len]), len),
+                    None => (Segment::Incomplete(self.rest), self.rest.len()),
+                },
+                len => {
+                    let s = unsafe { core::str::from_utf8_unchecked(&self.rest[..len]) };
+
+                    (Segment::Valid(s), len)
+                },
+            },
+        };
+
+        self.rest = &self.rest[len..];
+
+        Some(segment)
+    }
+}
+
+impl<'a> FusedIterator for ErrorRecovery<'a> {}
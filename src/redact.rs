@@ -0,0 +1,135 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// A builder which replaces registered literal patterns with a mask before the text is stored, even if a pattern is split across chunks.
+///
+/// The text which may still turn out to be a part of a pattern is held back until more data arrive or the redactor is finalized. When several patterns can match at the same position, the one registered first wins.
+#[derive(Debug, Clone)]
+pub struct Utf8Redactor {
+    /// the validated input which has not been scanned yet
+    input:    Utf8Builder,
+    /// the redacted text
+    output:   Utf8Builder,
+    patterns: Vec<String>,
+    mask:     String,
+}
+
+impl Utf8Redactor {
+    /// Constructs a new `Utf8Redactor` which replaces every matched pattern with `mask`.
+    #[inline]
+    pub fn new<S: Into<String>>(mask: S) -> Self {
+        Utf8Redactor {
+            input:    Utf8Builder::new(),
+            output:   Utf8Builder::new(),
+            patterns: Vec::new(),
+            mask:     mask.into(),
+        }
+    }
+
+    /// Registers a literal pattern. Empty patterns are ignored.
+    #[inline]
+    pub fn add_pattern<S: Into<String>>(&mut self, pattern: S) -> &mut Self {
+        let pattern = pattern.into();
+
+        if !pattern.is_empty() {
+            self.patterns.push(pattern);
+        }
+
+        self
+    }
+
+    /// Pushes a `&str`.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), Utf8Error> {
+        self.input.push_str(s)?;
+
+        self.scan(false)
+    }
+
+    /// Pushes a chunk.
+    #[inline]
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<(), Utf8Error> {
+        let result = self.input.push_chunk(chunk);
+
+        self.scan(false)?;
+
+        result.map(|_| ())
+    }
+
+    /// Extracts a string slice containing the redacted text which has been settled. The text held back for possible matches is excluded.
+    #[inline]
+    pub fn as_redacted_str(&self) -> &str {
+        self.output.as_valid_str()
+    }
+
+    /// Try to get the redacted `String` instance.
+    #[inline]
+    pub fn finalize(mut self) -> Result<String, Utf8Error> {
+        self.input.check_finalize()?;
+
+        self.scan(true)?;
+
+        self.output.finalize()
+    }
+
+    /// Moves the text which has been settled from the input to the output. If the output fails to grow, the text which has not been written stays in the input.
+    fn scan(&mut self, last: bool) -> Result<(), Utf8Error> {
+        let mut settled = 0;
+
+        let result = redact(
+            self.input.as_valid_str(),
+            &mut self.output,
+            &self.patterns,
+            &self.mask,
+            last,
+            &mut settled,
+        );
+
+        self.input.take_valid_to(settled);
+
+        result
+    }
+}
+
+/// Writes the redacted `text` to `output`. `settled` is advanced past the text which has been written.
+fn redact(
+    text: &str,
+    output: &mut Utf8Builder,
+    patterns: &[String],
+    mask: &str,
+    last: bool,
+    settled: &mut usize,
+) -> Result<(), Utf8Error> {
+    let mut i = 0;
+
+    'outer: while i < text.len() {
+        let rest = &text[i..];
+
+        for pattern in patterns.iter() {
+            if rest.starts_with(pattern.as_str()) {
+                output.push_str(&text[*settled..i])?;
+                *settled = i;
+
+                output.push_str(mask)?;
+
+                i += pattern.len();
+                *settled = i;
+
+                continue 'outer;
+            }
+
+            if !last && pattern.starts_with(rest) {
+                // more data are needed to determine whether this pattern matches
+                break 'outer;
+            }
+        }
+
+        i += rest.chars().next().map(char::len_utf8).unwrap_or(1);
+    }
+
+    output.push_str(&text[*settled..i])?;
+    *settled = i;
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use alloc::string::String;
+
+use crate::{PushOutcome, Utf8Builder, Utf8Error};
+
+/// A `Utf8Builder` which only keeps the lines accepted by a predicate. Every line is judged as soon as the push completing it returns, and the rejected lines are removed by compacting the pushed data in one pass, so they are only stored during that push. Created by `Utf8Builder::retain_lines`.
+#[derive(Debug, Clone)]
+pub struct RetainLines<F> {
+    builder:   Utf8Builder,
+    predicate: F,
+    /// the start of the current line, before which the lines have been judged
+    kept:      usize,
+    /// the position up to which the current line has been searched for its terminator
+    scanned:   usize,
+}
+
+impl Utf8Builder {
+    /// Wraps this builder so that every line completed from now on is passed to `predicate` without its `\n` or `\r\n` terminator, and is removed if `predicate` returns `false`. The lines which have been completed are kept.
+    #[inline]
+    pub fn retain_lines<F: FnMut(&str) -> bool>(self, predicate: F) -> RetainLines<F> {
+        let s = self.as_valid_str();
+
+        let kept = s.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let scanned = s.len();
+
+        RetainLines {
+            builder: self,
+            predicate,
+            kept,
+            scanned,
+        }
+    }
+}
+
+impl<F: FnMut(&str) -> bool> RetainLines<F> {
+    /// Gets a reference to the underlying builder, which also contains the current line that has not been judged.
+    #[inline]
+    pub fn builder(&self) -> &Utf8Builder {
+        &self.builder
+    }
+
+    /// Pushes a byte.
+    #[inline]
+    pub fn push(&mut self, b: u8) -> Result<PushOutcome, Utf8Error> {
+        let result = self.builder.push(b);
+
+        self.judge();
+
+        result
+    }
+
+    /// Pushes a `&str`.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), Utf8Error> {
+        let result = self.builder.push_str(s);
+
+        self.judge();
+
+        result
+    }
+
+    /// Pushes a char.
+    #[inline]
+    pub fn push_char(&mut self, c: char) -> Result<(), Utf8Error> {
+        let result = self.builder.push_char(c);
+
+        self.judge();
+
+        result
+    }
+
+    /// Pushes a chunk.
+    #[inline]
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<PushOutcome, Utf8Error> {
+        let result = self.builder.push_chunk(chunk);
+
+        self.judge();
+
+        result
+    }
+
+    /// Try to get the `String` instance of the kept lines. The last line without a terminator is judged as well.
+    pub fn finalize(mut self) -> Result<String, Utf8Error> {
+        self.builder.check_finalize()?;
+
+        let len = self.builder.len();
+
+        if self.kept < len && !(self.predicate)(&self.builder.as_valid_str()[self.kept..]) {
+            self.builder.thaw();
+            self.builder.buffer.truncate(self.kept);
+        }
+
+        self.builder.finalize()
+    }
+
+    /// Judges the completed lines which have not been judged, and moves the accepted ones over the rejected ones.
+    fn judge(&mut self) {
+        // the lines before `self.kept` are accepted, and the lines from `read` on have not been judged
+        let mut read = self.kept;
+
+        loop {
+            let s = self.builder.as_valid_str();
+
+            let end = match s[self.scanned..].find('\n') {
+                Some(i) => self.scanned + i + 1,
+                None => break,
+            };
+
+            let line = &s[read..end - 1];
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if (self.predicate)(line) {
+                if read > self.kept {
+                    self.builder.thaw();
+                    self.builder.buffer.copy_within(read..end, self.kept);
+                }
+
+                self.kept += end - read;
+            }
+
+            read = end;
+            self.scanned = end;
+        }
+
+        if read > self.kept {
+            // the current line and the incomplete character follow the accepted lines
+            self.builder.thaw();
+
+            let len = self.builder.buffer.len();
+
+            self.builder.buffer.copy_within(read..len, self.kept);
+            self.builder.buffer.truncate(self.kept + (len - read));
+        }
+
+        self.scanned = self.builder.valid_len();
+    }
+}
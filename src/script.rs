@@ -0,0 +1,69 @@
+use alloc::vec::Vec;
+use core::convert::Infallible;
+
+use unicode_script::{Script, UnicodeScript};
+
+use crate::TeeSink;
+
+/// A tally of characters by their Unicode scripts. It can be used as the sink of `Utf8Builder::tee` to count the characters as they complete.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScriptTally {
+    /// the counts in the order the scripts first appear
+    counts: Vec<(Script, u64)>,
+}
+
+impl ScriptTally {
+    /// Constructs a new, empty `ScriptTally`.
+    #[inline]
+    pub const fn new() -> Self {
+        ScriptTally {
+            counts: Vec::new()
+        }
+    }
+
+    /// Records the characters of a `&str`.
+    pub fn record(&mut self, s: &str) {
+        for c in s.chars() {
+            let script = c.script();
+
+            // a document usually contains only a few scripts, so a linear search is fast enough
+            match self.counts.iter_mut().find(|(s, _)| *s == script) {
+                Some((_, count)) => *count += 1,
+                None => self.counts.push((script, 1)),
+            }
+        }
+    }
+
+    /// Returns the number of characters in a script.
+    #[inline]
+    pub fn count(&self, script: Script) -> u64 {
+        self.counts.iter().find(|(s, _)| *s == script).map(|(_, count)| *count).unwrap_or(0)
+    }
+
+    /// Returns the counts of all the scripts which have appeared, in the order they first appeared.
+    #[inline]
+    pub fn counts(&self) -> &[(Script, u64)] {
+        &self.counts
+    }
+
+    /// Returns the script which has the most characters, ignoring `Common` and `Inherited`.
+    #[inline]
+    pub fn dominant(&self) -> Option<Script> {
+        self.counts
+            .iter()
+            .filter(|(s, _)| !matches!(s, Script::Common | Script::Inherited))
+            .max_by_key(|(_, count)| *count)
+            .map(|(s, _)| *s)
+    }
+}
+
+impl TeeSink for ScriptTally {
+    type Error = Infallible;
+
+    #[inline]
+    fn write_text(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.record(s);
+
+        Ok(())
+    }
+}
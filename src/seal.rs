@@ -0,0 +1,88 @@
+use alloc::sync::Arc;
+use core::{
+    fmt::{self, Display, Formatter},
+    ops::Deref,
+};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// Read-only UTF-8 data sealed by `Utf8Builder::seal`. It occupies exactly the length of the data, and clones share the same allocation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SealedUtf8 {
+    data: Arc<str>,
+}
+
+impl SealedUtf8 {
+    /// Extracts the string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.data
+    }
+
+    /// Extracts the byte slice.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.data.as_bytes()
+    }
+
+    /// Returns the length of the data, in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns `true` if there are no data.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl Deref for SealedUtf8 {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        &self.data
+    }
+}
+
+impl AsRef<str> for SealedUtf8 {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.data
+    }
+}
+
+impl AsRef<[u8]> for SealedUtf8 {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.data.as_bytes()
+    }
+}
+
+impl Display for SealedUtf8 {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.data)
+    }
+}
+
+impl From<SealedUtf8> for Arc<str> {
+    #[inline]
+    fn from(sealed: SealedUtf8) -> Self {
+        sealed.data
+    }
+}
+
+impl Utf8Builder {
+    /// Try to seal the data into a `SealedUtf8`, which is read-only, holds no spare capacity, and is cheap to clone. It suits caches holding lots of data which are rarely read.
+    #[inline]
+    pub fn seal(self) -> Result<SealedUtf8, Utf8Error> {
+        self.check_finalize()?;
+
+        Ok(SealedUtf8 {
+            data: Arc::from(self.as_valid_str())
+        })
+    }
+}
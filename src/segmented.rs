@@ -0,0 +1,149 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{state, Utf8Error, Utf8State};
+
+/// The default size of a segment of `SegmentedBuilder`.
+const DEFAULT_SEGMENT_SIZE: usize = 16 * 1024;
+
+/// A builder for bodies whose size is known up front (e.g. `Content-Length`). The chunks are validated and staged in fixed-size segments, and `finalize` allocates the `String` exactly once with the exact size.
+///
+/// The segments are allocated one at a time as data arrive, so a forged size does not make the builder allocate a huge buffer up front.
+#[derive(Debug, Clone)]
+pub struct SegmentedBuilder {
+    segments:     Vec<Vec<u8>>,
+    state:        Utf8State,
+    len:          usize,
+    expected_len: usize,
+    segment_size: usize,
+    /// whether a push has rejected data
+    failed:       bool,
+}
+
+impl SegmentedBuilder {
+    /// Constructs a new `SegmentedBuilder` expecting `expected_len` bytes, with 16 KiB segments.
+    #[inline]
+    pub fn new(expected_len: usize) -> Self {
+        Self::with_segment_size(expected_len, DEFAULT_SEGMENT_SIZE)
+    }
+
+    /// Constructs a new `SegmentedBuilder` expecting `expected_len` bytes, with segments of `segment_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segment_size` is 0.
+    #[inline]
+    pub fn with_segment_size(expected_len: usize, segment_size: usize) -> Self {
+        assert!(segment_size != 0, "segment size must be non-zero");
+
+        SegmentedBuilder {
+            segments: Vec::new(),
+            state: Utf8State::new(),
+            len: 0,
+            expected_len,
+            segment_size,
+            failed: false,
+        }
+    }
+
+    /// Returns the number of pushed bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been pushed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of expected bytes.
+    #[inline]
+    pub fn expected_len(&self) -> usize {
+        self.expected_len
+    }
+
+    /// Returns whether the current data are valid UTF-8
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        !self.failed && self.state.is_valid()
+    }
+
+    /// Pushes a chunk. Pushing more bytes than expected fails with `Utf8Error::LimitExceeded` without pushing anything. If the chunk is invalid, the bytes before the first invalid character are still pushed, and the later pushes fail.
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<(), Utf8Error> {
+        if self.failed {
+            return Err(Utf8Error::Invalid);
+        }
+
+        if chunk.len() > self.expected_len - self.len {
+            return Err(Utf8Error::LimitExceeded);
+        }
+
+        let (valid, result) = match state::validate(&mut self.state, chunk) {
+            Ok(_) => (chunk, Ok(())),
+            Err(valid_up_to) => {
+                self.failed = true;
+
+                (&chunk[..valid_up_to], Err(Utf8Error::Invalid))
+            },
+        };
+
+        self.stage(valid);
+
+        result
+    }
+
+    /// Try to get the `String` instance. Exactly one allocation of `self.len()` bytes is made.
+    ///
+    /// It fails with `Utf8Error::Incomplete` if fewer bytes than expected have been pushed, where `have` is the length of the incomplete character at the end and `need` is the number of the missing bytes (at most 255).
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        if self.failed {
+            return Err(Utf8Error::Invalid);
+        }
+
+        if self.len < self.expected_len {
+            return Err(Utf8Error::Incomplete {
+                have: self.state.pending().len() as u8,
+                need: (self.expected_len - self.len).min(u8::MAX as usize) as u8,
+            });
+        }
+
+        if !self.state.is_valid() {
+            return Err(Utf8Error::Incomplete {
+                have: self.state.pending().len() as u8,
+                need: self.state.needed() as u8,
+            });
+        }
+
+        let mut buffer = Vec::with_capacity(self.len);
+
+        for segment in self.segments.iter() {
+            buffer.extend_from_slice(segment);
+        }
+
+        Ok(unsafe { String::from_utf8_unchecked(buffer) })
+    }
+
+    fn stage(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            let segment = match self.segments.last_mut() {
+                Some(segment) if segment.len() < segment.capacity() => segment,
+                _ => {
+                    // the last segment is as large as the rest of the expected bytes
+                    let capacity = self.segment_size.min(self.expected_len - self.len);
+
+                    self.segments.push(Vec::with_capacity(capacity));
+
+                    self.segments.last_mut().unwrap()
+                },
+            };
+
+            let n = bytes.len().min(segment.capacity() - segment.len());
+
+            segment.extend_from_slice(&bytes[..n]);
+
+            self.len += n;
+            bytes = &bytes[n..];
+        }
+    }
+}
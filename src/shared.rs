@@ -0,0 +1,84 @@
+use alloc::{rc::Rc, string::String, sync::Arc};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// A shared string which can be pushed by `Utf8Builder::push_shared`.
+///
+/// Only `Arc<String>` and `Rc<String>` can give their text away without copying. The allocation of an `Arc<str>` or an `Rc<str>` also holds the reference counts, so its text is always copied.
+pub trait SharedStr: Sized {
+    /// Extracts a string slice.
+    fn as_str(&self) -> &str;
+
+    /// Takes the `String` out without copying if this is the only owner, or gives `self` back.
+    fn try_into_string(self) -> Result<String, Self>;
+}
+
+impl SharedStr for Arc<String> {
+    #[inline]
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn try_into_string(self) -> Result<String, Self> {
+        Arc::try_unwrap(self)
+    }
+}
+
+impl SharedStr for Rc<String> {
+    #[inline]
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn try_into_string(self) -> Result<String, Self> {
+        Rc::try_unwrap(self)
+    }
+}
+
+impl SharedStr for Arc<str> {
+    #[inline]
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn try_into_string(self) -> Result<String, Self> {
+        Err(self)
+    }
+}
+
+impl SharedStr for Rc<str> {
+    #[inline]
+    fn as_str(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn try_into_string(self) -> Result<String, Self> {
+        Err(self)
+    }
+}
+
+impl Utf8Builder {
+    /// Pushes a shared string. If the builder is empty and the string can be taken out of `s` (e.g. a uniquely owned `Arc<String>`), its buffer is taken without copying. Otherwise, the string is copied.
+    pub fn push_shared<S: SharedStr>(&mut self, s: S) -> Result<(), Utf8Error> {
+        if !self.is_empty() || self.error.is_some() {
+            return self.push_str(s.as_str());
+        }
+
+        match s.try_into_string() {
+            Ok(s) => self.poison_with(|builder| {
+                builder.check_limit(s.len())?;
+                builder.check_binary(s.as_bytes())?;
+
+                builder.replace_buffer(s.into_bytes());
+                builder.state = Default::default();
+
+                builder.check_profile(0)
+            }),
+            Err(s) => self.push_str(s.as_str()),
+        }
+    }
+}
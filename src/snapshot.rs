@@ -0,0 +1,71 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    ops::Deref,
+};
+use std::sync::Arc;
+
+/// An immutable snapshot of the complete characters of a `Utf8Builder`, created by `Utf8Builder::freeze`. Cloning it is cheap.
+#[derive(Clone)]
+pub struct Utf8Snapshot {
+    pub(crate) data: Arc<SharedBuffer>,
+    pub(crate) len:  usize,
+}
+
+/// The buffer shared by a `Utf8Builder` and its snapshots. If the `zeroize` feature is enabled, it is wiped by whichever owner drops it last.
+#[derive(Debug, Default)]
+pub(crate) struct SharedBuffer(pub(crate) Vec<u8>);
+
+impl SharedBuffer {
+    /// Takes the buffer out, leaving nothing to be wiped.
+    #[inline]
+    pub(crate) fn into_inner(mut self) -> Vec<u8> {
+        core::mem::take(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SharedBuffer {
+    #[inline]
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+impl Utf8Snapshot {
+    /// Extracts a string slice containing the snapshot.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(&self.data.0[..self.len]) }
+    }
+}
+
+impl Deref for Utf8Snapshot {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Utf8Snapshot {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Debug for Utf8Snapshot {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for Utf8Snapshot {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,101 @@
+use alloc::string::{String, ToString};
+
+use crate::Utf8Builder;
+
+/// A draining iterator over the whitespace-separated tokens of a `Utf8Builder` whose ends are certain. Created by `Utf8Builder::split_whitespace_stream`.
+///
+/// A token is yielded only after a whitespace character following it has arrived. The yielded tokens and the whitespace characters before the last token are removed from the builder when the iterator is dropped.
+#[derive(Debug)]
+pub struct SplitWhitespaceStream<'a> {
+    builder: &'a mut Utf8Builder,
+    /// the position where the next token is searched
+    pos:     usize,
+}
+
+impl Utf8Builder {
+    /// Creates a draining iterator over the whitespace-separated tokens which are certainly complete. The trailing token, which may continue in the next chunk, stays in the builder.
+    #[inline]
+    pub fn split_whitespace_stream(&mut self) -> SplitWhitespaceStream<'_> {
+        SplitWhitespaceStream {
+            builder: self, pos: 0
+        }
+    }
+}
+
+impl<'a> Iterator for SplitWhitespaceStream<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = &self.builder.as_valid_str()[self.pos..];
+
+        let start = match s.find(|c: char| !c.is_whitespace()) {
+            Some(start) => start,
+            None => {
+                self.pos += s.len();
+
+                return None;
+            },
+        };
+
+        self.pos += start;
+
+        let s = &s[start..];
+
+        let len = s.find(char::is_whitespace)?;
+
+        self.pos += len;
+
+        Some(s[..len].to_string())
+    }
+}
+
+impl<'a> Drop for SplitWhitespaceStream<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        self.builder.discard_valid_to(self.pos);
+    }
+}
+
+/// A draining iterator over the records of a `Utf8Builder` which end with a terminator, e.g. NUL-separated file names. Created by `Utf8Builder::split_terminator_drain`.
+///
+/// The yielded records and their terminators are removed from the builder when the iterator is dropped.
+#[derive(Debug)]
+pub struct SplitTerminatorDrain<'a> {
+    builder:    &'a mut Utf8Builder,
+    terminator: char,
+    /// the position where the next record begins
+    pos:        usize,
+}
+
+impl Utf8Builder {
+    /// Creates a draining iterator over the records which end with `terminator` (e.g. `'\0'` or `'\n'`), without the terminators. The unterminated tail, which may continue in the next chunk, stays in the builder.
+    #[inline]
+    pub fn split_terminator_drain(&mut self, terminator: char) -> SplitTerminatorDrain<'_> {
+        SplitTerminatorDrain {
+            builder: self,
+            terminator,
+            pos: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for SplitTerminatorDrain<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let s = &self.builder.as_valid_str()[self.pos..];
+
+        let len = s.find(self.terminator)?;
+
+        self.pos += len + self.terminator.len_utf8();
+
+        Some(s[..len].to_string())
+    }
+}
+
+impl<'a> Drop for SplitTerminatorDrain<'a> {
+    #[inline]
+    fn drop(&mut self) {
+        self.builder.discard_valid_to(self.pos);
+    }
+}
@@ -0,0 +1,127 @@
+use alloc::string::String;
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// A splitter for Server-Sent Events (`text/event-stream`). It validates UTF-8 data from chunks and splits it into events on blank lines, even if the blank line is spread across chunks.
+///
+/// `CRLF`, `LF` and `CR` are all accepted as line terminators. A leading BOM of the stream is removed.
+#[derive(Debug, Clone, Default)]
+pub struct SseSplitter {
+    builder:       Utf8Builder,
+    /// the position where the next scan starts
+    scanned:       usize,
+    /// whether `scanned` is at the beginning of a line
+    at_line_start: bool,
+    /// whether the leading BOM has been checked
+    bom_checked:   bool,
+}
+
+impl SseSplitter {
+    /// Constructs a new, empty `SseSplitter`.
+    #[inline]
+    pub const fn new() -> Self {
+        SseSplitter {
+            builder:       Utf8Builder::new(),
+            scanned:       0,
+            at_line_start: true,
+            bom_checked:   false,
+        }
+    }
+
+    /// Pushes a chunk.
+    #[inline]
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<(), Utf8Error> {
+        self.builder.push_chunk(chunk).map(|_| ())
+    }
+
+    /// Takes out the next complete event. The event text excludes the blank line which terminates it, and events with no lines are skipped.
+    pub fn next_event(&mut self) -> Option<String> {
+        if !self.bom_checked {
+            const BOM: &[u8] = "\u{FEFF}".as_bytes();
+
+            let buffer = self.builder.bytes();
+            let n = buffer.len().min(BOM.len());
+
+            if buffer[..n] == BOM[..n] {
+                if n < BOM.len() {
+                    // wait for more bytes to see whether this is a BOM
+                    return None;
+                }
+
+                self.builder.take_valid_to(BOM.len());
+            }
+
+            self.bom_checked = true;
+        }
+
+        loop {
+            let bytes = self.builder.as_valid_str().as_bytes();
+            let len = bytes.len();
+
+            let mut i = self.scanned;
+            let mut cut = None;
+
+            while i < len {
+                let end = match bytes[i] {
+                    b'\n' => i + 1,
+                    b'\r' => {
+                        if i + 1 == len {
+                            // wait for the next byte to see whether this is a `CRLF`
+                            break;
+                        }
+
+                        if bytes[i + 1] == b'\n' {
+                            i + 2
+                        } else {
+                            i + 1
+                        }
+                    },
+                    _ => {
+                        self.at_line_start = false;
+                        i += 1;
+
+                        continue;
+                    },
+                };
+
+                if self.at_line_start {
+                    cut = Some((i, end));
+
+                    break;
+                }
+
+                self.at_line_start = true;
+                i = end;
+            }
+
+            self.scanned = i;
+
+            let (blank_line_start, end) = cut?;
+
+            let mut event = self.builder.take_valid_to(end);
+
+            event.truncate(blank_line_start);
+
+            // remove the terminator of the last line
+            if event.ends_with('\n') {
+                event.pop();
+            }
+
+            if event.ends_with('\r') {
+                event.pop();
+            }
+
+            self.scanned = 0;
+
+            if !event.is_empty() {
+                return Some(event);
+            }
+        }
+    }
+
+    /// Consumes this splitter and returns the remaining data which are not terminated by a blank line. According to the specification, an unterminated event should be discarded.
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        self.builder.finalize()
+    }
+}
@@ -0,0 +1,378 @@
+use crate::Utf8Error;
+
+/// The validation state between chunks, which records the incomplete character at the end of the data validated so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Utf8State {
+    /// the bytes of the incomplete character
+    pending: [u8; 3],
+    /// the length for the incomplete character
+    sl:      u8,
+    /// the valid expected length for the incomplete character
+    sel:     u8,
+}
+
+/// How a validated chunk is split by character boundaries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ValidSplit {
+    /// The number of leading bytes of the chunk which end on a character boundary. It includes the bytes which complete an incomplete character from the previous chunks.
+    pub complete: usize,
+    /// The number of trailing bytes of the chunk which belong to an incomplete character.
+    pub pending:  usize,
+}
+
+/// The lengths of a validated byte buffer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CharLen {
+    /// The number of the bytes.
+    pub bytes:       usize,
+    /// The number of the complete characters.
+    pub chars:       usize,
+    /// Whether the bytes end on a character boundary. If not, the trailing bytes are the beginning of a valid character.
+    pub on_boundary: bool,
+}
+
+/// Whether the data end on a character boundary after a push.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PushOutcome {
+    /// The data end on a character boundary.
+    Complete,
+    /// The data end with an incomplete character, which needs this many more bytes.
+    NeedMore(u8),
+}
+
+impl Utf8State {
+    /// Constructs a new `Utf8State` which is on a character boundary.
+    #[inline]
+    pub const fn new() -> Self {
+        Utf8State {
+            pending: [0; 3], sl: 0, sel: 0
+        }
+    }
+
+    /// Returns whether the validated data end on a character boundary.
+    #[inline]
+    pub const fn is_valid(&self) -> bool {
+        self.sl == 0
+    }
+
+    /// Returns the bytes of the incomplete character.
+    #[inline]
+    pub fn pending(&self) -> &[u8] {
+        &self.pending[..self.sl as usize]
+    }
+
+    /// Returns how many bytes are still needed to complete the incomplete character.
+    #[inline]
+    pub const fn needed(&self) -> usize {
+        if self.sl == 0 {
+            0
+        } else {
+            (self.sel - self.sl) as usize
+        }
+    }
+
+    /// Returns whether the validated data end on a character boundary, or how many more bytes are needed.
+    #[inline]
+    pub const fn outcome(&self) -> PushOutcome {
+        if self.sl == 0 {
+            PushOutcome::Complete
+        } else {
+            PushOutcome::NeedMore(self.sel - self.sl)
+        }
+    }
+
+    /// Validates a byte. The state is not changed if the byte is invalid.
+    pub(crate) fn push(&mut self, b: u8) -> Result<(), Utf8Error> {
+        if self.sl == 0 {
+            let w = utf8_width::get_width(b);
+
+            match w {
+                0 => return Err(Utf8Error::Invalid),
+                1 => (),
+                _ => {
+                    self.pending[0] = b;
+                    self.sl = 1;
+                    self.sel = w as u8;
+                },
+            }
+        } else {
+            let (min, max) =
+                if self.sl == 1 { second_byte_range(self.pending[0]) } else { (0x80, 0xBF) };
+
+            if b < min || b > max {
+                return Err(Utf8Error::Invalid);
+            }
+
+            if self.sl + 1 == self.sel {
+                self.sl = 0;
+                // self.sel = 0; // no need
+            } else {
+                self.pending[self.sl as usize] = b;
+                self.sl += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the range of the second byte of a character. The second byte is restricted to avoid overlong forms, surrogates and code points beyond U+10FFFF.
+#[inline]
+pub(crate) const fn second_byte_range(lead: u8) -> (u8, u8) {
+    match lead {
+        0xE0 => (0xA0, 0xBF),
+        0xED => (0x80, 0x9F),
+        0xF0 => (0x90, 0xBF),
+        0xF4 => (0x80, 0x8F),
+        _ => (0x80, 0xBF),
+    }
+}
+
+/// Observes the validation paths taken by `validate_with`. `enter` is called before a path is taken, and the other methods after it, with the number of its bytes.
+pub(crate) trait Probe {
+    #[inline]
+    fn enter(&mut self) {}
+
+    #[inline]
+    fn stitch(&mut self, _bytes: usize) {}
+
+    #[inline]
+    #[cfg_attr(not(all(feature = "sse2", target_arch = "x86_64")), allow(dead_code))]
+    fn ascii(&mut self, _bytes: usize) {}
+
+    #[inline]
+    fn multibyte(&mut self, _bytes: usize) {}
+}
+
+impl Probe for () {}
+
+/// Validates a chunk with the state of the previous chunks. If the chunk is invalid, `Err(valid_up_to)` is returned, and the state is updated as if only `&chunk[..valid_up_to]` had been validated.
+#[inline]
+pub(crate) fn validate(state: &mut Utf8State, chunk: &[u8]) -> Result<ValidSplit, usize> {
+    validate_with(state, chunk, &mut ())
+}
+
+/// Validates a chunk like `validate`, reporting every path taken to the probe.
+pub(crate) fn validate_with<P: Probe>(
+    state: &mut Utf8State,
+    chunk: &[u8],
+    probe: &mut P,
+) -> Result<ValidSplit, usize> {
+    let stitching = !state.is_valid();
+
+    probe.enter();
+
+    let result = stitch(state, chunk);
+
+    if stitching {
+        probe.stitch(match result {
+            Ok(Some(i)) => i,
+            Ok(None) => chunk.len(),
+            Err(_) => 0,
+        });
+    }
+
+    let i = match result? {
+        Some(i) => i,
+        None => {
+            return Ok(ValidSplit {
+                complete: 0, pending: chunk.len()
+            })
+        },
+    };
+
+    #[cfg(all(feature = "sse2", target_arch = "x86_64"))]
+    return validate_runs(state, chunk, i, probe);
+
+    #[cfg(not(all(feature = "sse2", target_arch = "x86_64")))]
+    {
+        probe.enter();
+
+        validate_rest(state, chunk, i, probe)
+    }
+}
+
+/// Validates `&chunk[start..]`, where the state is on a character boundary, by skipping every ASCII run with vectors and validating only the multi-byte characters between them.
+#[cfg(all(feature = "sse2", target_arch = "x86_64"))]
+fn validate_runs<P: Probe>(
+    state: &mut Utf8State,
+    chunk: &[u8],
+    start: usize,
+    probe: &mut P,
+) -> Result<ValidSplit, usize> {
+    let mut i = start;
+
+    loop {
+        probe.enter();
+
+        let ascii = ascii_prefix_len(&chunk[i..]);
+
+        if ascii > 0 {
+            probe.ascii(ascii);
+        }
+
+        i += ascii;
+
+        probe.enter();
+
+        // a multi-byte character never contains an ASCII byte, so the run of the other bytes ends on a character boundary unless it reaches the end of the chunk
+        let end = match chunk[i..].iter().position(u8::is_ascii) {
+            Some(n) => i + n,
+            None => return validate_rest(state, chunk, i, probe),
+        };
+
+        let result = core::str::from_utf8(&chunk[i..end]);
+
+        probe.multibyte(end - i);
+
+        if let Err(err) = result {
+            return Err(i + err.valid_up_to());
+        }
+
+        i = end;
+    }
+}
+
+/// Validates `&chunk[start..]` with `validate_from`, reporting it to the probe as the multi-byte path. The probe has been entered.
+#[inline]
+fn validate_rest<P: Probe>(
+    state: &mut Utf8State,
+    chunk: &[u8],
+    start: usize,
+    probe: &mut P,
+) -> Result<ValidSplit, usize> {
+    let result = validate_from(state, chunk, start);
+
+    if start < chunk.len() {
+        probe.multibyte(chunk.len() - start);
+    }
+
+    result
+}
+
+/// Completes the incomplete character of the state with the leading bytes of a chunk. Returns the number of the used bytes, or `None` if the character is still incomplete at the end of the chunk. If the character cannot be completed, `Err(0)` is returned and the state is not changed.
+#[inline]
+pub(crate) fn stitch(state: &mut Utf8State, chunk: &[u8]) -> Result<Option<usize>, usize> {
+    let mut i = 0;
+
+    if state.sl > 0 {
+        let origin = *state;
+
+        while i < chunk.len() {
+            if state.push(chunk[i]).is_err() {
+                *state = origin;
+
+                return Err(0);
+            }
+
+            i += 1;
+
+            if state.sl == 0 {
+                break;
+            }
+        }
+
+        if state.sl > 0 {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some(i))
+}
+
+/// Validates `&chunk[start..]`, where the state is on a character boundary.
+#[inline]
+pub(crate) fn validate_from(
+    state: &mut Utf8State,
+    chunk: &[u8],
+    start: usize,
+) -> Result<ValidSplit, usize> {
+    match core::str::from_utf8(&chunk[start..]) {
+        Ok(_) => Ok(ValidSplit {
+            complete: chunk.len(), pending: 0
+        }),
+        Err(err) => {
+            let valid_up_to = start + err.valid_up_to();
+
+            if err.error_len().is_some() {
+                return Err(valid_up_to);
+            }
+
+            // the rest bytes are the beginning of a valid character
+            for b in chunk[valid_up_to..].iter().copied() {
+                state.push(b).map_err(|_| valid_up_to)?;
+            }
+
+            Ok(ValidSplit {
+                complete: valid_up_to, pending: chunk.len() - valid_up_to
+            })
+        },
+    }
+}
+
+/// Returns the length of the leading ASCII bytes. SSE2 is part of the x86_64 baseline, so no runtime detection is needed.
+#[cfg(all(feature = "sse2", target_arch = "x86_64"))]
+#[inline]
+fn ascii_prefix_len(bytes: &[u8]) -> usize {
+    use core::arch::x86_64::{__m128i, _mm_loadu_si128, _mm_movemask_epi8};
+
+    const LANES: usize = 16;
+
+    let mut i = 0;
+
+    while i + LANES <= bytes.len() {
+        // the load is unaligned and within the slice, and the bits of the mask are the high bits of the bytes
+        let mask =
+            unsafe { _mm_movemask_epi8(_mm_loadu_si128(bytes.as_ptr().add(i) as *const __m128i)) };
+
+        if mask != 0 {
+            return i + mask.trailing_zeros() as usize;
+        }
+
+        i += LANES;
+    }
+
+    i + bytes[i..].iter().take_while(|b| b.is_ascii()).count()
+}
+
+/// Validates a chunk with the state of the previous chunks, for the data which are stored outside a `Utf8Builder`. The chunk does not have to end on a character boundary.
+///
+/// If the chunk is invalid, the state is updated as if only the bytes before the first invalid character had been validated.
+#[inline]
+pub fn validate_chunk(state: &mut Utf8State, chunk: &[u8]) -> Result<ValidSplit, Utf8Error> {
+    validate(state, chunk).map_err(|_| Utf8Error::Invalid)
+}
+
+/// Validates a standalone byte buffer without constructing a `Utf8Builder`, and counts its characters. The buffer does not have to end on a character boundary.
+///
+/// ```rust
+/// let len = utf8_builder::validate_and_count(&"a中".as_bytes()[..3]).unwrap();
+///
+/// assert_eq!(3, len.bytes);
+/// assert_eq!(1, len.chars);
+/// assert!(!len.on_boundary);
+/// ```
+#[inline]
+pub fn validate_and_count(bytes: &[u8]) -> Result<CharLen, Utf8Error> {
+    let mut state = Utf8State::new();
+
+    let split = validate(&mut state, bytes).map_err(|_| Utf8Error::Invalid)?;
+
+    let chars = bytes[..split.complete].iter().filter(|&&b| (b as i8) >= -0x40).count();
+
+    Ok(CharLen {
+        bytes: bytes.len(),
+        chars,
+        on_boundary: split.pending == 0,
+    })
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Utf8State {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.pending.zeroize();
+        self.sl.zeroize();
+        self.sel.zeroize();
+    }
+}
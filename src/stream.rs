@@ -0,0 +1,97 @@
+use alloc::string::String;
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_core::{ready, Stream};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// An adapter which turns a stream of byte chunks into a stream of `String`s. Each item contains only complete characters, and an incomplete character at the end of an upstream chunk is carried forward to the next item.
+#[derive(Debug)]
+pub struct Utf8Stream<S> {
+    stream:  S,
+    builder: Utf8Builder,
+    done:    bool,
+}
+
+impl<S> Utf8Stream<S> {
+    /// Wraps a stream of byte chunks.
+    #[inline]
+    pub const fn new(stream: S) -> Self {
+        Utf8Stream {
+            stream,
+            builder: Utf8Builder::new(),
+            done: false,
+        }
+    }
+
+    /// Gets a reference to the underlying stream.
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Consumes this adapter, returning the underlying stream.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S: Stream> Stream for Utf8Stream<S>
+where
+    S::Item: AsRef<[u8]>,
+{
+    type Item = Result<String, Utf8Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // `stream` is the only structurally pinned field and it is never moved out
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            let stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+            match ready!(stream.poll_next(cx)) {
+                Some(chunk) => {
+                    if let Err(err) = this.builder.push_chunk(chunk.as_ref()) {
+                        this.done = true;
+
+                        return Poll::Ready(Some(Err(err)));
+                    }
+
+                    let s = this.builder.take_valid();
+
+                    if !s.is_empty() {
+                        return Poll::Ready(Some(Ok(s)));
+                    }
+                },
+                None => {
+                    this.done = true;
+
+                    return match this.builder.check_finalize() {
+                        Ok(()) => Poll::Ready(None),
+                        Err(err) => Poll::Ready(Some(Err(err))),
+                    };
+                },
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            // every upstream chunk yields at most one item, plus one error at the end
+            let (_, upper) = self.stream.size_hint();
+
+            (0, upper.and_then(|n| n.checked_add(1)))
+        }
+    }
+}
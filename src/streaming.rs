@@ -0,0 +1,54 @@
+use core::num::NonZeroUsize;
+
+use crate::{state, Utf8Builder, Utf8State};
+
+/// An error shaped like the errors of streaming parsers (e.g. `nom::Err`), which tells invalid data from data which merely need more bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamingError {
+    /// The data are invalid no matter what bytes come next.
+    Invalid,
+    /// The data end in the middle of a character, and at least this many more bytes are needed.
+    Incomplete(NonZeroUsize),
+}
+
+#[cfg(feature = "nom")]
+impl StreamingError {
+    /// Converts into a `nom::Err`. `Invalid` becomes `nom::Err::Error` with `ErrorKind::Verify` at `input`, and `Incomplete` becomes `nom::Err::Incomplete` with the needed size.
+    #[inline]
+    pub fn into_nom<I, E: nom::error::ParseError<I>>(self, input: I) -> nom::Err<E> {
+        match self {
+            StreamingError::Invalid => {
+                nom::Err::Error(E::from_error_kind(input, nom::error::ErrorKind::Verify))
+            },
+            StreamingError::Incomplete(n) => nom::Err::Incomplete(nom::Needed::Size(n)),
+        }
+    }
+}
+
+#[inline]
+fn check_state(state: &Utf8State) -> Result<(), StreamingError> {
+    match NonZeroUsize::new(state.needed()) {
+        Some(n) => Err(StreamingError::Incomplete(n)),
+        None => Ok(()),
+    }
+}
+
+impl Utf8Builder {
+    /// Returns `Ok(())` if the data end on a character boundary, or how many more bytes are needed at least.
+    #[inline]
+    pub fn check_complete(&self) -> Result<(), StreamingError> {
+        check_state(self.state())
+    }
+}
+
+/// Validates a standalone byte slice with the semantics of streaming parsers. An incomplete character at the end is reported as `StreamingError::Incomplete` instead of being invalid.
+#[inline]
+pub fn validate_streaming(bytes: &[u8]) -> Result<&str, StreamingError> {
+    let mut state = Utf8State::new();
+
+    state::validate(&mut state, bytes).map_err(|_| StreamingError::Invalid)?;
+
+    check_state(&state)?;
+
+    Ok(unsafe { core::str::from_utf8_unchecked(bytes) })
+}
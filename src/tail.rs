@@ -0,0 +1,111 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{state, Utf8Builder, Utf8Error};
+
+/// A builder which validates the whole stream but retains only its last bytes, e.g. to capture the end of a huge command output for diagnostics. The retained data always begin on a character boundary.
+#[derive(Debug, Clone)]
+pub struct TailBuilder {
+    builder: Utf8Builder,
+    window:  usize,
+    /// the number of bytes accepted so far, including the dropped ones
+    total:   u64,
+}
+
+impl TailBuilder {
+    /// Constructs a new `TailBuilder` which retains the complete characters within the last `window` bytes.
+    #[inline]
+    pub fn new(window: usize) -> Self {
+        TailBuilder {
+            builder: Utf8Builder::new(),
+            window,
+            total: 0,
+        }
+    }
+
+    /// Returns the maximum number of the retained bytes.
+    #[inline]
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the number of bytes accepted so far, including the dropped ones.
+    #[inline]
+    pub fn total_len(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns whether the current data are valid UTF-8
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.builder.error().is_none() && self.builder.is_valid()
+    }
+
+    /// Extracts a string slice containing the retained complete characters.
+    #[inline]
+    pub fn as_valid_str(&self) -> &str {
+        self.builder.as_valid_str()
+    }
+
+    /// Pushes a `&str`.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), Utf8Error> {
+        self.push_chunk(s.as_bytes())
+    }
+
+    /// Pushes a chunk. Only the part of the chunk which can be retained is copied, and the rest is only validated. If the chunk is invalid, the later pushes fail.
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<(), Utf8Error> {
+        if let Some(err) = self.builder.error() {
+            return Err(err.kind);
+        }
+
+        let (head, tail) = chunk.split_at(chunk.len().saturating_sub(self.window));
+
+        if !head.is_empty() {
+            self.builder.check_binary(head)?;
+
+            let mut state = self.builder.state;
+
+            match state::validate(&mut state, head) {
+                Ok(_) => {
+                    // all the retained data are out of the window, except the incomplete character at the end of `head`
+                    self.builder.replace_buffer(Vec::from(state.pending()));
+                    self.builder.state = state;
+
+                    self.total += head.len() as u64;
+                },
+                Err(valid_up_to) => {
+                    self.push_chunk(&head[..valid_up_to])?;
+
+                    return self.builder.poison(Utf8Error::Invalid, &head[valid_up_to..]);
+                },
+            }
+        }
+
+        let len = self.builder.len();
+
+        let result = self.builder.push_chunk(tail);
+
+        self.total += (self.builder.len() - len) as u64;
+
+        self.trim();
+
+        result.map(|_| ())
+    }
+
+    /// Try to get the retained data as a `String`.
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        self.builder.finalize()
+    }
+
+    /// Drops the complete characters which are out of the window.
+    fn trim(&mut self) {
+        let len = self.builder.len();
+
+        if len > self.window {
+            let end = self.builder.ceil_char_boundary(len - self.window);
+
+            self.builder.discard_valid_to(end);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+#[cfg(feature = "std")]
+use std::{error::Error, io};
+
+use crate::{PushOutcome, Utf8Builder, Utf8Error};
+
+/// A secondary sink which receives the complete characters accepted by a `Utf8Tee`.
+pub trait TeeSink {
+    /// The error type of the sink.
+    type Error;
+
+    /// Writes complete characters.
+    fn write_text(&mut self, s: &str) -> Result<(), Self::Error>;
+}
+
+/// A `TeeSink` wrapping a `fmt::Write`.
+#[derive(Debug, Clone, Default)]
+pub struct FmtSink<W>(pub W);
+
+impl<W: Write> TeeSink for FmtSink<W> {
+    type Error = fmt::Error;
+
+    #[inline]
+    fn write_text(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.0.write_str(s)
+    }
+}
+
+/// A `TeeSink` wrapping an `io::Write`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct IoSink<W>(pub W);
+
+#[cfg(feature = "std")]
+impl<W: io::Write> TeeSink for IoSink<W> {
+    type Error = io::Error;
+
+    #[inline]
+    fn write_text(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.0.write_all(s.as_bytes())
+    }
+}
+
+/// Errors which can occur when pushing data into a `Utf8Tee`.
+#[derive(Debug)]
+pub enum TeeError<E> {
+    /// The data are not valid UTF-8. The complete characters before the invalid data have still been written.
+    Utf8(Utf8Error),
+    /// The sink failed.
+    Sink(E),
+}
+
+impl<E> From<Utf8Error> for TeeError<E> {
+    #[inline]
+    fn from(error: Utf8Error) -> Self {
+        TeeError::Utf8(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Display> fmt::Display for TeeError<E> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TeeError::Utf8(err) => fmt::Display::fmt(err, f),
+            TeeError::Sink(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Error> Error for TeeError<E> {}
+
+/// A `Utf8Builder` which also writes every complete character it accepts into a secondary sink. Created by `Utf8Builder::tee`.
+#[derive(Debug, Clone)]
+pub struct Utf8Tee<S> {
+    builder: Utf8Builder,
+    sink:    S,
+}
+
+impl Utf8Builder {
+    /// Wraps this builder so that every complete character accepted from now on is also written into `sink`.
+    #[inline]
+    pub fn tee<S: TeeSink>(self, sink: S) -> Utf8Tee<S> {
+        Utf8Tee {
+            builder: self,
+            sink,
+        }
+    }
+}
+
+impl<S: TeeSink> Utf8Tee<S> {
+    /// Gets a reference to the underlying builder.
+    #[inline]
+    pub fn builder(&self) -> &Utf8Builder {
+        &self.builder
+    }
+
+    /// Gets a reference to the sink.
+    #[inline]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Pushes a byte. Returns whether the data end on a character boundary afterwards.
+    #[inline]
+    pub fn push(&mut self, b: u8) -> Result<PushOutcome, TeeError<S::Error>> {
+        self.tee_with(|builder| builder.push(b))
+    }
+
+    /// Pushes a `&str`.
+    #[inline]
+    pub fn push_str(&mut self, s: &str) -> Result<(), TeeError<S::Error>> {
+        self.tee_with(|builder| builder.push_str(s))
+    }
+
+    /// Pushes a char.
+    #[inline]
+    pub fn push_char(&mut self, c: char) -> Result<(), TeeError<S::Error>> {
+        self.tee_with(|builder| builder.push_char(c))
+    }
+
+    /// Pushes a chunk. Returns whether the data end on a character boundary afterwards.
+    #[inline]
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<PushOutcome, TeeError<S::Error>> {
+        self.tee_with(|builder| builder.push_chunk(chunk))
+    }
+
+    /// Try to get the `String` instance. The sink is dropped.
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        self.builder.finalize()
+    }
+
+    /// Consumes this `Utf8Tee`, returning the builder and the sink.
+    #[inline]
+    pub fn into_parts(self) -> (Utf8Builder, S) {
+        (self.builder, self.sink)
+    }
+
+    fn tee_with<T>(
+        &mut self,
+        f: impl FnOnce(&mut Utf8Builder) -> Result<T, Utf8Error>,
+    ) -> Result<T, TeeError<S::Error>> {
+        let start = self.builder.valid_len();
+
+        let result = f(&mut self.builder);
+
+        let text = &self.builder.as_valid_str()[start..];
+
+        if !text.is_empty() {
+            self.sink.write_text(text).map_err(TeeError::Sink)?;
+        }
+
+        result.map_err(TeeError::Utf8)
+    }
+}
@@ -0,0 +1,304 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{Utf8Builder, Utf8Decoder, Utf8Error};
+
+/// A transformation stage of a `TransformPipeline`. A stage receives complete characters chunk by chunk, and it has to carry its own state across chunks.
+pub trait Stage {
+    /// Transforms `input` and appends the result to `output`.
+    fn process(&mut self, input: &str, output: &mut String);
+
+    /// Appends the data held back by the stage to `output`, when there is no more input.
+    #[inline]
+    fn finish(&mut self, _output: &mut String) {}
+}
+
+/// A stage which converts `CRLF` and `CR` into `LF`.
+#[derive(Debug, Clone, Default)]
+pub struct NormalizeNewlines {
+    /// whether the previous character is a `CR`
+    after_cr: bool,
+}
+
+impl NormalizeNewlines {
+    /// Constructs a new `NormalizeNewlines`.
+    #[inline]
+    pub const fn new() -> Self {
+        NormalizeNewlines {
+            after_cr: false
+        }
+    }
+}
+
+impl Stage for NormalizeNewlines {
+    fn process(&mut self, input: &str, output: &mut String) {
+        for c in input.chars() {
+            match c {
+                '\r' => {
+                    output.push('\n');
+
+                    self.after_cr = true;
+                },
+                '\n' if self.after_cr => {
+                    self.after_cr = false;
+                },
+                _ => {
+                    output.push(c);
+
+                    self.after_cr = false;
+                },
+            }
+        }
+    }
+}
+
+/// A stage which folds the case of characters by `char::to_lowercase`.
+#[derive(Debug, Clone, Default)]
+pub struct CaseFold;
+
+impl Stage for CaseFold {
+    #[inline]
+    fn process(&mut self, input: &str, output: &mut String) {
+        output.extend(input.chars().flat_map(char::to_lowercase));
+    }
+}
+
+/// A stage which collapses every run of whitespace characters into a single space, even if the run is split across chunks.
+#[derive(Debug, Clone, Default)]
+pub struct CollapseWhitespace {
+    /// whether the previous character is a whitespace character
+    in_whitespace: bool,
+}
+
+impl CollapseWhitespace {
+    /// Constructs a new `CollapseWhitespace`.
+    #[inline]
+    pub const fn new() -> Self {
+        CollapseWhitespace {
+            in_whitespace: false
+        }
+    }
+}
+
+impl Stage for CollapseWhitespace {
+    fn process(&mut self, input: &str, output: &mut String) {
+        for c in input.chars() {
+            if c.is_whitespace() {
+                if !self.in_whitespace {
+                    output.push(' ');
+
+                    self.in_whitespace = true;
+                }
+            } else {
+                output.push(c);
+
+                self.in_whitespace = false;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AnsiState {
+    Normal,
+    /// after `ESC`
+    Escape,
+    /// inside `ESC [`
+    Csi,
+    /// inside `ESC ]`
+    Osc,
+    /// after `ESC` inside `ESC ]`
+    OscEscape,
+}
+
+/// A stage which removes ANSI escape sequences (CSI sequences such as colors, OSC sequences such as titles, and two-character escape sequences).
+#[derive(Debug, Clone)]
+pub struct StripAnsi {
+    state: AnsiState,
+}
+
+impl StripAnsi {
+    /// Constructs a new `StripAnsi`.
+    #[inline]
+    pub const fn new() -> Self {
+        StripAnsi {
+            state: AnsiState::Normal
+        }
+    }
+}
+
+impl Default for StripAnsi {
+    #[inline]
+    fn default() -> Self {
+        StripAnsi::new()
+    }
+}
+
+impl Stage for StripAnsi {
+    fn process(&mut self, input: &str, output: &mut String) {
+        for c in input.chars() {
+            self.state = match self.state {
+                AnsiState::Normal => {
+                    if c == '\x1B' {
+                        AnsiState::Escape
+                    } else {
+                        output.push(c);
+
+                        AnsiState::Normal
+                    }
+                },
+                AnsiState::Escape => match c {
+                    '[' => AnsiState::Csi,
+                    ']' => AnsiState::Osc,
+                    _ => AnsiState::Normal,
+                },
+                AnsiState::Csi => {
+                    if ('\x40'..='\x7E').contains(&c) {
+                        AnsiState::Normal
+                    } else {
+                        AnsiState::Csi
+                    }
+                },
+                AnsiState::Osc => match c {
+                    '\x07' => AnsiState::Normal,
+                    '\x1B' => AnsiState::OscEscape,
+                    _ => AnsiState::Osc,
+                },
+                AnsiState::OscEscape => {
+                    if c == '\\' {
+                        AnsiState::Normal
+                    } else {
+                        AnsiState::Osc
+                    }
+                },
+            };
+        }
+    }
+
+    #[inline]
+    fn finish(&mut self, _output: &mut String) {
+        // an unterminated escape sequence is discarded
+        self.state = AnsiState::Normal;
+    }
+}
+
+#[derive(Debug)]
+enum Input {
+    Strict(Utf8Builder),
+    Lossy(Utf8Decoder),
+}
+
+/// A builder which passes data through a chain of transformation stages before accumulating them.
+///
+/// The data are validated (or decoded lossily) first, and then the complete characters of every chunk go through the stages in the order they were added.
+pub struct TransformPipeline {
+    input:  Input,
+    stages: Vec<Box<dyn Stage>>,
+    output: Utf8Builder,
+}
+
+impl TransformPipeline {
+    /// Constructs a new `TransformPipeline` which rejects invalid UTF-8 data.
+    #[inline]
+    pub fn new() -> Self {
+        TransformPipeline {
+            input:  Input::Strict(Utf8Builder::new()),
+            stages: Vec::new(),
+            output: Utf8Builder::new(),
+        }
+    }
+
+    /// Constructs a new `TransformPipeline` which replaces invalid UTF-8 data with U+FFFD.
+    #[inline]
+    pub fn new_lossy() -> Self {
+        TransformPipeline {
+            input:  Input::Lossy(Utf8Decoder::new()),
+            stages: Vec::new(),
+            output: Utf8Builder::new(),
+        }
+    }
+
+    /// Appends a stage to the chain.
+    #[inline]
+    pub fn stage<S: Stage + 'static>(mut self, stage: S) -> Self {
+        self.stages.push(Box::new(stage));
+
+        self
+    }
+
+    /// Extracts a string slice containing the transformed text so far.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.output.as_valid_str()
+    }
+
+    /// Pushes a chunk. In the strict mode, the characters before the first invalid character still go through the stages.
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<(), Utf8Error> {
+        let mut result = Ok(());
+
+        let text = match &mut self.input {
+            Input::Strict(builder) => {
+                result = builder.push_chunk(chunk).map(|_| ());
+
+                builder.take_valid()
+            },
+            Input::Lossy(decoder) => {
+                let mut text =
+                    String::with_capacity(decoder.max_utf8_buffer_length(chunk.len()).unwrap());
+
+                decoder.decode_to_string(chunk, &mut text, false);
+
+                text
+            },
+        };
+
+        self.run(text, false)?;
+
+        result
+    }
+
+    /// Try to get the transformed `String` instance.
+    pub fn finalize(mut self) -> Result<String, Utf8Error> {
+        let text = match &mut self.input {
+            Input::Strict(builder) => {
+                builder.check_finalize()?;
+
+                String::new()
+            },
+            Input::Lossy(decoder) => {
+                let mut text = String::with_capacity(decoder.max_utf8_buffer_length(0).unwrap());
+
+                decoder.decode_to_string(b"", &mut text, true);
+
+                text
+            },
+        };
+
+        self.run(text, true)?;
+
+        self.output.finalize()
+    }
+
+    /// Passes `text` through the stages and pushes the result into the output.
+    fn run(&mut self, mut text: String, last: bool) -> Result<(), Utf8Error> {
+        for stage in self.stages.iter_mut() {
+            let mut output = String::with_capacity(text.len());
+
+            stage.process(&text, &mut output);
+
+            if last {
+                stage.finish(&mut output);
+            }
+
+            text = output;
+        }
+
+        self.output.push_str(&text)
+    }
+}
+
+impl Default for TransformPipeline {
+    #[inline]
+    fn default() -> Self {
+        TransformPipeline::new()
+    }
+}
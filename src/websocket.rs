@@ -0,0 +1,133 @@
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::fmt::{self, Display, Formatter};
+
+use crate::Utf8Builder;
+
+/// Errors which can occur when assembling a WebSocket text message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebSocketTextError {
+    /// The payload data of the message are not valid UTF-8.
+    InvalidUtf8,
+    /// A continuation frame is received but no message is in progress.
+    UnexpectedContinuation,
+    /// A new text frame is received before the previous message is finished.
+    UnfinishedMessage,
+}
+
+impl WebSocketTextError {
+    /// Returns the status code which should be used to close the connection, as defined in RFC 6455.
+    #[inline]
+    pub const fn close_code(&self) -> u16 {
+        match self {
+            WebSocketTextError::InvalidUtf8 => 1007,
+            WebSocketTextError::UnexpectedContinuation | WebSocketTextError::UnfinishedMessage => {
+                1002
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Display for WebSocketTextError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WebSocketTextError::InvalidUtf8 => f.write_str("incorrect UTF-8 data"),
+            WebSocketTextError::UnexpectedContinuation => {
+                f.write_str("unexpected continuation frame")
+            },
+            WebSocketTextError::UnfinishedMessage => f.write_str("unfinished text message"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for WebSocketTextError {}
+
+/// A validator for WebSocket text messages. It assembles the payload data of a text frame and its continuation frames, and validates them as UTF-8 data, even if a character is split across frames.
+#[derive(Debug, Clone, Default)]
+pub struct WebSocketTextValidator {
+    builder:     Utf8Builder,
+    /// whether a text message is in progress
+    in_progress: bool,
+}
+
+impl WebSocketTextValidator {
+    /// Constructs a new `WebSocketTextValidator`.
+    #[inline]
+    pub const fn new() -> Self {
+        WebSocketTextValidator {
+            builder: Utf8Builder::new(), in_progress: false
+        }
+    }
+
+    /// Returns whether a text message has been started but not finished yet.
+    #[inline]
+    pub fn is_in_progress(&self) -> bool {
+        self.in_progress
+    }
+
+    /// Pushes the payload data of a text frame (opcode `0x1`). If `fin` is `true`, the complete message is returned.
+    #[inline]
+    pub fn push_text_frame(
+        &mut self,
+        payload: &[u8],
+        fin: bool,
+    ) -> Result<Option<String>, WebSocketTextError> {
+        if self.in_progress {
+            self.reset();
+
+            return Err(WebSocketTextError::UnfinishedMessage);
+        }
+
+        self.in_progress = true;
+
+        self.push_payload(payload, fin)
+    }
+
+    /// Pushes the payload data of a continuation frame (opcode `0x0`). If `fin` is `true`, the complete message is returned.
+    #[inline]
+    pub fn push_continuation_frame(
+        &mut self,
+        payload: &[u8],
+        fin: bool,
+    ) -> Result<Option<String>, WebSocketTextError> {
+        if !self.in_progress {
+            return Err(WebSocketTextError::UnexpectedContinuation);
+        }
+
+        self.push_payload(payload, fin)
+    }
+
+    /// Discards the message in progress.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.builder = Utf8Builder::new();
+        self.in_progress = false;
+    }
+
+    fn push_payload(
+        &mut self,
+        payload: &[u8],
+        fin: bool,
+    ) -> Result<Option<String>, WebSocketTextError> {
+        if self.builder.push_chunk(payload).is_err() {
+            self.reset();
+
+            return Err(WebSocketTextError::InvalidUtf8);
+        }
+
+        if fin {
+            let builder = core::mem::take(&mut self.builder);
+
+            self.in_progress = false;
+
+            builder.finalize().map(Some).map_err(|_| WebSocketTextError::InvalidUtf8)
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use alloc::string::String;
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// A builder which accumulates UTF-16 code units (e.g. the wide-character buffers from `ReadConsoleW` or other Win32 APIs) into UTF-8. A surrogate pair split across buffers is joined.
+#[derive(Debug, Clone, Default)]
+pub struct WideBuilder {
+    builder: Utf8Builder,
+    /// the high surrogate at the end of the previous buffer
+    high:    Option<u16>,
+}
+
+impl WideBuilder {
+    /// Constructs a new, empty `WideBuilder`.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_builder(Utf8Builder::new())
+    }
+
+    /// Constructs a new `WideBuilder` which continues building from an existing `Utf8Builder`.
+    #[inline]
+    pub const fn with_builder(builder: Utf8Builder) -> Self {
+        WideBuilder {
+            builder,
+            high: None,
+        }
+    }
+
+    /// Gets a reference to the underlying builder.
+    #[inline]
+    pub fn builder(&self) -> &Utf8Builder {
+        &self.builder
+    }
+
+    /// Returns whether the current data are valid, with no incomplete character or surrogate pair.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.high.is_none() && self.builder.is_valid()
+    }
+
+    /// Pushes UTF-16 code units. A high surrogate at the end is kept to be paired with the next buffer. An unpaired surrogate poisons the underlying builder, but the characters before it are still pushed.
+    pub fn push_wide(&mut self, units: &[u16]) -> Result<(), Utf8Error> {
+        // the characters are encoded into a small buffer first, so that the builder is not called for every character
+        let mut buf = [0; 256];
+        let mut len = 0;
+
+        let mut iter =
+            char::decode_utf16(self.high.take().into_iter().chain(units.iter().copied()))
+                .peekable();
+
+        while let Some(result) = iter.next() {
+            let c = match result {
+                Ok(c) => c,
+                Err(err) => {
+                    let unit = err.unpaired_surrogate();
+
+                    if iter.peek().is_none() && (0xD800..0xDC00).contains(&unit) {
+                        self.high = Some(unit);
+
+                        break;
+                    }
+
+                    self.flush(&buf[..len])?;
+
+                    return self.builder.poison(Utf8Error::Invalid, &[]);
+                },
+            };
+
+            if len + 4 > buf.len() {
+                self.flush(&buf[..len])?;
+
+                len = 0;
+            }
+
+            len += c.encode_utf8(&mut buf[len..]).len();
+        }
+
+        self.flush(&buf[..len])
+    }
+
+    /// Try to get the `String` instance. A pending high surrogate is reported as `Utf8Error::Incomplete` with the 2 bytes of the code unit and the 2 bytes of the missing low surrogate.
+    #[inline]
+    pub fn finalize(self) -> Result<String, Utf8Error> {
+        if self.high.is_some() {
+            return Err(Utf8Error::Incomplete {
+                have: 2, need: 2
+            });
+        }
+
+        self.builder.finalize()
+    }
+
+    /// Consumes this `WideBuilder`, returning the underlying builder. A pending high surrogate is discarded.
+    #[inline]
+    pub fn into_inner(self) -> Utf8Builder {
+        self.builder
+    }
+
+    #[inline]
+    fn flush(&mut self, bytes: &[u8]) -> Result<(), Utf8Error> {
+        self.builder.push_str(unsafe { core::str::from_utf8_unchecked(bytes) })
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io::{self, Write},
+};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// Errors which can occur when finalizing a `Utf8Builder` into a writer.
+#[derive(Debug)]
+pub enum FinalizeError {
+    /// The data are not valid UTF-8. Nothing has been written.
+    Utf8(Utf8Error),
+    /// The writer failed.
+    Io(io::Error),
+}
+
+impl From<Utf8Error> for FinalizeError {
+    #[inline]
+    fn from(error: Utf8Error) -> Self {
+        FinalizeError::Utf8(error)
+    }
+}
+
+impl From<io::Error> for FinalizeError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        FinalizeError::Io(error)
+    }
+}
+
+impl Display for FinalizeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            FinalizeError::Utf8(err) => Display::fmt(err, f),
+            FinalizeError::Io(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl Error for FinalizeError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FinalizeError::Utf8(err) => Some(err),
+            FinalizeError::Io(err) => Some(err),
+        }
+    }
+}
+
+impl Utf8Builder {
+    /// Checks the data are complete and writes them into `w` without producing a `String`. Returns the number of written bytes.
+    #[inline]
+    pub fn finalize_to_writer<W: Write>(self, w: W) -> Result<u64, FinalizeError> {
+        let len = self.len();
+
+        self.finalize_to_writer_chunked(w, len.max(1))
+    }
+
+    /// Checks the data are complete and writes them into `w` by calls of `write_all` with at most `chunk_size` bytes each. Returns the number of written bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn finalize_to_writer_chunked<W: Write>(
+        self,
+        mut w: W,
+        chunk_size: usize,
+    ) -> Result<u64, FinalizeError> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+
+        self.check_finalize()?;
+
+        let bytes = self.bytes();
+
+        for chunk in bytes.chunks(chunk_size) {
+            w.write_all(chunk)?;
+        }
+
+        w.flush()?;
+
+        Ok(bytes.len() as u64)
+    }
+}
@@ -0,0 +1,70 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use crate::{Utf8Builder, Utf8Error};
+
+/// A future pushing a chunk into a `Utf8Builder` a piece at a time, which yields to the executor after every piece. Created by `Utf8Builder::push_chunk_yielding`.
+#[derive(Debug)]
+pub struct YieldingPush<'a, 'b> {
+    builder: &'a mut Utf8Builder,
+    chunk:   &'b [u8],
+    budget:  usize,
+    started: bool,
+}
+
+impl Future for YieldingPush<'_, '_> {
+    type Output = Result<(), Utf8Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if !this.started {
+            this.started = true;
+
+            // the whole chunk is checked so that nothing is pushed if the limit would be exceeded
+            if let Err(err) = this.builder.check_limit(this.chunk.len()) {
+                return Poll::Ready(Err(err));
+            }
+        }
+
+        let (piece, rest) = this.chunk.split_at(this.budget.min(this.chunk.len()));
+
+        this.chunk = rest;
+
+        if let Err(err) = this.builder.push_chunk(piece) {
+            return Poll::Ready(Err(err));
+        }
+
+        if this.chunk.is_empty() {
+            Poll::Ready(Ok(()))
+        } else {
+            cx.waker().wake_by_ref();
+
+            Poll::Pending
+        }
+    }
+}
+
+impl Utf8Builder {
+    /// Pushes a chunk in an async task, validating at most `budget` bytes per poll and yielding to the executor in between, so that a very large chunk does not block the executor. If the chunk is invalid, the bytes before the first invalid character are still pushed.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is not cancel safe. If the future is dropped before it completes (e.g. in a losing branch of `select!`), the pieces pushed so far stay in the builder and the rest of the chunk is not pushed. The number of the pushed bytes can be told by comparing `Utf8Builder::len` before and after.
+    #[inline]
+    pub fn push_chunk_yielding<'a, 'b>(
+        &'a mut self,
+        chunk: &'b [u8],
+        budget: usize,
+    ) -> YieldingPush<'a, 'b> {
+        YieldingPush {
+            builder: self,
+            chunk,
+            budget: budget.max(1),
+            started: false,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use utf8_builder::{BinaryDetection, Utf8Builder, Utf8Error};
+
+#[test]
+fn binary_detection() {
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(Some(BinaryDetection::default()));
+
+    assert_eq!(Err(Utf8Error::LooksBinary), builder.push_chunk(b"\x7FELF\x02\x01\x01\x00"));
+    assert_eq!(Err(Utf8Error::LooksBinary), builder.push_str("text"));
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(Some(BinaryDetection::default()));
+
+    assert_eq!(Err(Utf8Error::LooksBinary), builder.push_chunk(b"\x89PNG\r\n\x1A\n\xFF\xFE"));
+
+    // a few invalid bytes are reported as invalid data
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(Some(BinaryDetection::default()));
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk(b"This is some text.\xFF"));
+
+    // the bytes beyond the window are not inspected
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(Some(BinaryDetection::new(4, 0)));
+    builder.push_chunk(b"abc").unwrap();
+    builder.push_chunk(b"d\x00").unwrap();
+    builder.push(0).unwrap();
+
+    assert_eq!("abcd\0\0", builder.finalize().unwrap());
+}
+
+#[test]
+fn binary_detection_running_ratio() {
+    // the ratio is counted over all the inspected bytes, however the data are split
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(Some(BinaryDetection::default()));
+
+    for &b in b"This is some text." {
+        builder.push(b).unwrap();
+    }
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push(0xFF));
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(Some(BinaryDetection::new(16, 20)));
+    builder.push_str_lossy_from_bytes(b"text\xFF").unwrap();
+
+    assert_eq!(Err(Utf8Error::LooksBinary), builder.push_str_lossy_from_bytes(b"\xFE\xFD"));
+}
+
+#[test]
+fn binary_detection_every_push() {
+    let detection = Some(BinaryDetection::default());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(detection);
+
+    assert_eq!(Err(Utf8Error::LooksBinary), builder.push_repeat_char('\0', 3));
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(detection);
+
+    assert_eq!(Err(Utf8Error::LooksBinary), builder.append(Utf8Builder::from("a\0b")));
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(detection);
+
+    assert_eq!(Err(Utf8Error::LooksBinary), builder.push_shared(Arc::new(String::from("a\0b"))));
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_binary_detection(detection);
+
+    assert_eq!(Err(Utf8Error::LooksBinary), builder.push_str_lossy_from_bytes(b"a\0b"));
+}
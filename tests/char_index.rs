@@ -0,0 +1,31 @@
+use utf8_builder::Utf8Builder;
+
+#[test]
+fn byte_offset_of_char() {
+    let text = "a中😀b文字ccc😃";
+
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(&text.as_bytes()[..5]).unwrap();
+
+    assert_eq!(Some(1), builder.byte_offset_of_char(1));
+    assert_eq!(None, builder.byte_offset_of_char(2));
+
+    for interval in 0..=12 {
+        let mut builder = Utf8Builder::from("a").with_char_index(interval);
+
+        for b in text.as_bytes()[1..].iter().copied() {
+            builder.push(b).unwrap();
+        }
+
+        assert_eq!(interval.max(1), builder.interval());
+        assert_eq!(text.chars().count(), builder.char_count());
+
+        for (n, (i, _)) in text.char_indices().enumerate() {
+            assert_eq!(Some(i), builder.byte_offset_of_char(n), "{} {}", interval, n);
+        }
+
+        assert_eq!(None, builder.byte_offset_of_char(builder.char_count()));
+        assert_eq!(text, builder.finalize().unwrap());
+    }
+}
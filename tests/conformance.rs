@@ -0,0 +1,60 @@
+use utf8_builder::{Strictness, Violation, ViolationKind};
+
+fn check(data: &[u8], strictness: Strictness) -> Result<(), Violation> {
+    utf8_builder::check_conformance(data, strictness)
+}
+
+fn kind(data: &[u8]) -> Option<ViolationKind> {
+    check(data, Strictness::STRICT).err().map(|violation| violation.kind)
+}
+
+#[test]
+fn check_conformance() {
+    assert_eq!(Ok(()), check("a中😀".as_bytes(), Strictness::STRICT));
+
+    assert_eq!(Some(ViolationKind::UnexpectedByte), kind(b"\x80"));
+    assert_eq!(Some(ViolationKind::UnexpectedByte), kind(b"\xF8\x80\x80\x80"));
+    assert_eq!(Some(ViolationKind::UnexpectedByte), kind(b"\xE4\xB8a"));
+    assert_eq!(Some(ViolationKind::Overlong), kind(b"\xC0\xAF"));
+    assert_eq!(Some(ViolationKind::Overlong), kind(b"\xE0\x80\xAF"));
+    assert_eq!(Some(ViolationKind::Overlong), kind(b"\xF0\x80\x80\xAF"));
+    assert_eq!(Some(ViolationKind::Surrogate), kind(b"\xED\xBF\xBF"));
+    assert_eq!(Some(ViolationKind::OutOfRange), kind(b"\xF4\x90\x80\x80"));
+    assert_eq!(Some(ViolationKind::OutOfRange), kind(b"\xF5\x80\x80\x80"));
+    assert_eq!(Some(ViolationKind::Truncated), kind(b"\xF0\x9F\x98"));
+
+    assert_eq!(
+        Err(Violation {
+            offset: 4, kind: ViolationKind::Overlong
+        }),
+        check(b"abcd\xC1\xBF", Strictness::ALLOW_SURROGATES)
+    );
+
+    let relaxed = Strictness {
+        overlong: false, surrogate: false, out_of_range: false
+    };
+
+    assert_eq!(Ok(()), check(b"\xC0\xAF\xED\xA0\x80\xF7\xBF\xBF\xBF", relaxed));
+    assert_eq!(Some(ViolationKind::Truncated), check(b"\xC0", relaxed).err().map(|v| v.kind));
+}
+
+#[test]
+fn check_conformance_agrees_with_std() {
+    let mut data = [0u8; 4];
+
+    for a in 0x80..=0xFFu8 {
+        for b in (0x00..=0xFFu8).step_by(0x10) {
+            data[0] = a;
+            data[1] = b;
+            data[2] = 0x80;
+            data[3] = 0x80;
+
+            assert_eq!(
+                std::str::from_utf8(&data).is_ok(),
+                check(&data, Strictness::STRICT).is_ok(),
+                "{:02X?}",
+                data
+            );
+        }
+    }
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "const-validation")]
+
+use utf8_builder::{str_from_static, validate_const, validate_slice_const};
+
+const TABLE: Option<&str> = str_from_static("a中😀".as_bytes());
+const BAD: bool = validate_const(*b"\xED\xA0\x80");
+
+#[test]
+fn const_validation() {
+    assert_eq!(Some("a中😀"), TABLE);
+    assert!(!BAD);
+
+    for &bytes in &[
+        &b"\xE4A"[..],
+        b"\xC0\x80",
+        b"\xE0\x80\x80",
+        b"\xED\xA0\x80",
+        b"\xF4\x90\x80\x80",
+        b"\xF5\x80\x80\x80",
+        b"\x80",
+        b"\xE4\xB8",
+    ] {
+        assert!(!validate_slice_const(bytes));
+    }
+
+    assert!(validate_const([]));
+    assert_eq!(None, str_from_static(b"a\xFF"));
+}
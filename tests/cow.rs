@@ -0,0 +1,26 @@
+use std::borrow::Cow;
+
+use utf8_builder::{CowBuilder, PushOutcome};
+
+#[test]
+fn cow_builder() {
+    let template = String::from("Hello, 世界");
+
+    let mut builder = CowBuilder::new(&template);
+
+    builder.push_str("").unwrap();
+
+    assert_eq!(PushOutcome::Complete, builder.push_chunk(b"").unwrap());
+    assert!(builder.is_borrowed());
+    assert!(matches!(builder.clone().finalize(), Ok(Cow::Borrowed("Hello, 世界"))));
+
+    assert_eq!(PushOutcome::NeedMore(2), builder.push_chunk(&"！".as_bytes()[..1]).unwrap());
+    assert!(!builder.is_borrowed());
+    assert_eq!("Hello, 世界", builder.as_valid_str());
+    assert!(builder.clone().finalize().is_err());
+
+    builder.push_chunk(&"！".as_bytes()[1..]).unwrap();
+    builder.push_char('!').unwrap();
+
+    assert!(matches!(builder.finalize(), Ok(Cow::Owned(s)) if s == "Hello, 世界！!"));
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "stream")]
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{executor::block_on, future, stream, StreamExt};
+use utf8_builder::{AccumulateFailure, Utf8Builder, Utf8Error};
+
+/// A deadline which passes on the `n`-th poll.
+struct PollDeadline(usize);
+
+impl Future for PollDeadline {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.0 -= 1;
+
+        if self.0 == 0 {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn accumulate_with_deadline() {
+    let chunks = stream::iter(vec![&"中文".as_bytes()[..2], &"中文".as_bytes()[2..], b"ab"]);
+
+    assert_eq!(
+        "中文ab",
+        block_on(Utf8Builder::new().accumulate_with_deadline(chunks, future::pending::<()>()))
+            .unwrap()
+    );
+
+    let chunks = stream::iter(vec![&b"ab"[..], b"cd"]).chain(stream::pending());
+
+    let err =
+        block_on(Utf8Builder::new().accumulate_with_deadline(chunks, PollDeadline(3))).unwrap_err();
+
+    assert_eq!(AccumulateFailure::DeadlineExceeded, err.failure);
+    assert_eq!(2, err.chunks);
+    assert_eq!(4, err.bytes);
+    assert_eq!("abcd", err.builder.as_valid_str());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_limit(Some(3));
+
+    let chunks = stream::iter(vec![&b"ab"[..], b"cd"]);
+
+    let err =
+        block_on(builder.accumulate_with_deadline(chunks, future::pending::<()>())).unwrap_err();
+
+    assert_eq!(AccumulateFailure::Utf8(Utf8Error::LimitExceeded), err.failure);
+    assert_eq!("ab", err.builder.as_valid_str());
+
+    let chunks = stream::iter(vec![&b"a\xE4"[..]]);
+
+    let err =
+        block_on(Utf8Builder::new().accumulate_with_deadline(chunks, future::pending::<()>()))
+            .unwrap_err();
+
+    assert_eq!(
+        AccumulateFailure::Utf8(Utf8Error::Incomplete {
+            have: 1, need: 2
+        }),
+        err.failure
+    );
+    assert_eq!(1, err.chunks);
+}
+
+#[test]
+fn accumulate_with_deadline_ready_stream() {
+    // a stream which is always ready is still stopped by the deadline
+    let chunks = stream::repeat(&b"ab"[..]);
+
+    let err =
+        block_on(Utf8Builder::new().accumulate_with_deadline(chunks, PollDeadline(5))).unwrap_err();
+
+    assert_eq!(AccumulateFailure::DeadlineExceeded, err.failure);
+    assert_eq!(4, err.chunks);
+    assert_eq!("abababab", err.builder.as_valid_str());
+}
@@ -0,0 +1,97 @@
+use utf8_builder::{CoderResult, DecoderResult, Utf8Decoder};
+
+#[test]
+fn decode_to_string() {
+    let input = "Hello, 世界! 😀".as_bytes();
+
+    for chunk_size in 1..=8 {
+        let mut decoder = Utf8Decoder::new();
+        let mut output =
+            String::with_capacity(decoder.max_utf8_buffer_length(input.len()).unwrap());
+
+        for chunk in input.chunks(chunk_size) {
+            let (result, read, had_replacements) =
+                decoder.decode_to_string(chunk, &mut output, false);
+
+            assert_eq!(
+                (CoderResult::InputEmpty, chunk.len(), false),
+                (result, read, had_replacements)
+            );
+        }
+
+        assert_eq!(
+            (CoderResult::InputEmpty, 0, false),
+            decoder.decode_to_string(b"", &mut output, true)
+        );
+        assert_eq!("Hello, 世界! 😀", output);
+    }
+
+    let mut decoder = Utf8Decoder::new();
+    let mut output = String::with_capacity(64);
+
+    assert_eq!(
+        (CoderResult::InputEmpty, 6, true),
+        decoder.decode_to_string(b"a\xE4\xB8b\xFF\xF0", &mut output, true)
+    );
+    assert_eq!("a\u{FFFD}b\u{FFFD}\u{FFFD}", output);
+}
+
+#[test]
+fn decode_to_string_output_full() {
+    let input = "中文字".as_bytes();
+
+    let mut decoder = Utf8Decoder::new();
+    let mut output = String::with_capacity(4);
+
+    let (result, read, _) = decoder.decode_to_string(input, &mut output, true);
+
+    assert_eq!(CoderResult::OutputFull, result);
+    assert_eq!("中", output);
+
+    let mut output2 = String::with_capacity(6);
+
+    assert_eq!(
+        (CoderResult::InputEmpty, input.len() - read, false),
+        decoder.decode_to_string(&input[read..], &mut output2, true)
+    );
+    assert_eq!("文字", output2);
+}
+
+#[test]
+fn decode_to_string_without_replacement() {
+    let mut decoder = Utf8Decoder::new();
+    let mut output = String::with_capacity(64);
+
+    assert_eq!(
+        (DecoderResult::InputEmpty, 2),
+        decoder.decode_to_string_without_replacement(b"a\xE4", &mut output, false)
+    );
+    assert_eq!(
+        (DecoderResult::Malformed(1, 0), 0),
+        decoder.decode_to_string_without_replacement(b"b", &mut output, false)
+    );
+    assert_eq!(
+        (DecoderResult::InputEmpty, 1),
+        decoder.decode_to_string_without_replacement(b"b", &mut output, true)
+    );
+    assert_eq!("ab", output);
+}
+
+#[test]
+fn decode_to_string_output_full_at_error() {
+    let mut decoder = Utf8Decoder::new();
+    let mut output = String::with_capacity(2);
+
+    let (result, read, had_replacements) = decoder.decode_to_string(b"a\xFFb", &mut output, true);
+
+    assert_eq!((CoderResult::OutputFull, 2, false), (result, read, had_replacements));
+    assert_eq!("a", output);
+
+    let mut output2 = String::with_capacity(8);
+
+    assert_eq!(
+        (CoderResult::InputEmpty, 1, true),
+        decoder.decode_to_string(b"b", &mut output2, true)
+    );
+    assert_eq!("\u{FFFD}b", output2);
+}
@@ -0,0 +1,24 @@
+use utf8_builder::{PushOutcome, Utf8Demux, Utf8Error};
+
+#[test]
+fn utf8_demux() {
+    let mut demux = Utf8Demux::new(10);
+
+    assert_eq!(PushOutcome::NeedMore(1), demux.push_chunk(1, &"中".as_bytes()[..2]).unwrap());
+    assert_eq!(PushOutcome::Complete, demux.push_chunk(3, b"abc").unwrap());
+    assert_eq!(PushOutcome::Complete, demux.push_chunk(1, &"中".as_bytes()[2..]).unwrap());
+    assert_eq!(6, demux.used());
+    assert_eq!(2, demux.len());
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), demux.push_chunk(5, b"defgh"));
+    assert_eq!(2, demux.len());
+
+    assert_eq!(Err(Utf8Error::Invalid), demux.push_chunk(3, b"d\xFF"));
+    assert_eq!(7, demux.used());
+    assert_eq!("中", demux.finalize(&1).unwrap().unwrap());
+    assert_eq!(4, demux.used());
+    assert_eq!(Err(Utf8Error::Invalid), demux.finalize(&3).unwrap());
+    assert_eq!(None, demux.finalize(&3));
+    assert_eq!(0, demux.used());
+    assert!(demux.is_empty());
+}
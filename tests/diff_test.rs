@@ -0,0 +1,23 @@
+#![cfg(feature = "diff-test")]
+
+use utf8_builder::{Profile, Utf8Builder};
+
+#[test]
+fn diff_test() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push(b'a').unwrap();
+    builder.push_str("中").unwrap();
+    builder.push_chunk(&"文😀".as_bytes()[..5]).unwrap();
+    builder.push_chunk(&"😀".as_bytes()[2..]).unwrap();
+    builder.push_chunk(b"b\xE4\xFF").unwrap_err();
+
+    assert_eq!("a中文😀b", builder.as_valid_str());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_profile(Profile::Strict);
+    builder.push_chunk("a\u{0}b".as_bytes()).unwrap_err();
+
+    assert_eq!("a", builder.as_valid_str());
+}
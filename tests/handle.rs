@@ -0,0 +1,35 @@
+#![cfg(feature = "handles")]
+
+use utf8_builder::{ResultCode, Utf8Handles};
+
+#[test]
+fn handles() {
+    let mut handles = Utf8Handles::new();
+
+    let a = handles.create();
+    let b = handles.create();
+
+    assert_eq!(2, handles.len());
+    assert_eq!(ResultCode::Ok, handles.push(a, "中".as_bytes()[..2].to_vec()));
+    assert_eq!(ResultCode::Ok, handles.push(b, b"xyz".to_vec()));
+    assert_eq!(ResultCode::Ok, handles.push(a, "中".as_bytes()[2..].to_vec()));
+    assert_eq!(Ok(String::from("中")), handles.finish(a));
+    assert_eq!(Err(ResultCode::BadHandle), handles.finish(a));
+    assert_eq!(ResultCode::BadHandle, handles.push(a, Vec::new()));
+    assert_eq!(ResultCode::BadHandle, handles.push(100, Vec::new()));
+
+    let c = handles.create();
+
+    // the slot is reused, but the stale handle does not reach the new builder
+    assert_ne!(a, c);
+    assert_eq!(a as u32, c as u32);
+    assert_eq!(ResultCode::BadHandle, handles.push(a, b"a".to_vec()));
+    assert_eq!(ResultCode::BadHandle, handles.discard(a));
+    assert_eq!(ResultCode::Ok, handles.set_limit(c, 2));
+    assert_eq!(ResultCode::LimitExceeded, handles.push(c, b"abc".to_vec()));
+    assert_eq!(ResultCode::Invalid, handles.push(c, b"\xFF".to_vec()));
+    assert_eq!(1, ResultCode::Invalid as i32);
+    assert_eq!(Err(ResultCode::Invalid), handles.finish(c));
+    assert_eq!(ResultCode::Ok, handles.discard(b));
+    assert!(handles.is_empty());
+}
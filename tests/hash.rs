@@ -0,0 +1,27 @@
+#![cfg(feature = "sha2")]
+
+use utf8_builder::Utf8Builder;
+
+#[test]
+fn finalize_with_hash() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(&"中文".as_bytes()[..4]).unwrap();
+
+    let mut tee = builder.hashed();
+
+    tee.push_chunk(&"中文".as_bytes()[4..]).unwrap();
+    tee.push_str("abc").unwrap();
+
+    let (s, hash) = tee.finalize_with_hash().unwrap();
+
+    assert_eq!("中文abc", s);
+    assert_eq!(
+        [
+            0x0F, 0x3F, 0x66, 0xD4, 0x22, 0x3B, 0xA8, 0x50, 0xA7, 0x75, 0xF6, 0xFA, 0xC6, 0x66,
+            0xED, 0x72, 0x65, 0xEB, 0xA9, 0xC8, 0x8C, 0x98, 0x67, 0xC0, 0x36, 0x79, 0xA1, 0xC2,
+            0x81, 0x25, 0xB8, 0x9F
+        ],
+        hash
+    );
+}
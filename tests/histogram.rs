@@ -0,0 +1,20 @@
+use utf8_builder::{ByteHistogram, Utf8Builder};
+
+#[test]
+fn byte_histogram() {
+    let text = "aé中é";
+
+    let mut tee = Utf8Builder::new().tee(ByteHistogram::new());
+
+    for chunk in text.as_bytes().chunks(2) {
+        tee.push_chunk(chunk).unwrap();
+    }
+
+    let (builder, histogram) = tee.into_parts();
+
+    assert_eq!(text, builder.finalize().unwrap());
+    assert_eq!(text.len() as u64, histogram.total());
+    assert_eq!(1, histogram.count(b'a'));
+    assert_eq!(2, histogram.count(0xC3));
+    assert_eq!(0, histogram.count(b'b'));
+}
@@ -0,0 +1,34 @@
+use std::cell::RefCell;
+
+use utf8_builder::{PushError, Utf8Builder, Utf8Error};
+
+#[test]
+fn with_hooks() {
+    let overflows = RefCell::new(Vec::new());
+    let errors = RefCell::new(Vec::new());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_limit(Some(6));
+    builder.set_error_context(2);
+
+    let mut builder = builder.with_hooks((
+        |offset, additional| overflows.borrow_mut().push((offset, additional)),
+        |error: &PushError| errors.borrow_mut().push(error.clone()),
+    ));
+
+    builder.push_str("ab").unwrap();
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), builder.push_str("中文"));
+    assert_eq!(vec![(2, 6)], *overflows.borrow());
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk(b"c\xFF"));
+    assert_eq!(Err(Utf8Error::Invalid), builder.push(b'd'));
+
+    let errors = errors.borrow();
+
+    assert_eq!(1, errors.len());
+    assert_eq!(3, errors[0].offset);
+    assert_eq!(b"bc", errors[0].context.as_ref().unwrap().before.as_slice());
+    assert_eq!(b"\xFF", errors[0].context.as_ref().unwrap().after.as_slice());
+}
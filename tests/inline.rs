@@ -0,0 +1,42 @@
+use utf8_builder::{InlineBuilder, PushOutcome, Utf8Error};
+
+#[test]
+fn inline_builder() {
+    let mut builder = InlineBuilder::<8>::new();
+
+    builder.push_str("a中").unwrap();
+
+    assert_eq!(PushOutcome::NeedMore(3), builder.push(0xF0).unwrap());
+    assert_eq!("a中", builder.as_valid_str());
+    assert!(!builder.is_valid());
+    assert!(builder.push_str("b").is_err());
+
+    let mut builder = InlineBuilder::<8>::new();
+
+    builder.push_str("a中").unwrap();
+    builder.push_chunk(b"\xF0\x9F").unwrap();
+    builder.push_chunk(b"\x98\x80").unwrap();
+
+    assert!(builder.is_inline());
+    assert_eq!(8, builder.len());
+    assert_eq!("a中😀", builder.clone().finalize().unwrap());
+
+    builder.push_char('!').unwrap();
+
+    assert!(!builder.is_inline());
+    assert_eq!("a中😀!", builder.finalize().unwrap());
+
+    let mut builder = InlineBuilder::<16>::default();
+
+    builder.push_chunk(b"ab\xE4").unwrap();
+
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 1, need: 2
+        }),
+        builder.clone().finalize()
+    );
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk(b"c"));
+    assert!(!builder.is_inline());
+    assert_eq!(Some(2), builder.into_builder().error().map(|err| err.offset));
+}
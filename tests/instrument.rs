@@ -0,0 +1,60 @@
+#![cfg(feature = "validation-profile")]
+
+use utf8_builder::Utf8Builder;
+
+#[test]
+fn validation_profile() {
+    let mut builder = Utf8Builder::new();
+
+    assert!(builder.validation_profile().is_none());
+
+    builder.set_validation_profiling(true);
+
+    builder.push_chunk(&"abc中".as_bytes()[..4]).unwrap();
+    builder.push_chunk(&"中d".as_bytes()[1..]).unwrap();
+    builder.push_chunk(b"efg").unwrap();
+
+    let profile = *builder.validation_profile().unwrap();
+
+    assert_eq!(1, profile.stitch.calls);
+    assert_eq!(2, profile.stitch.bytes);
+
+    if cfg!(all(feature = "sse2", target_arch = "x86_64")) {
+        assert_eq!(3, profile.ascii.calls);
+        assert_eq!(7, profile.ascii.bytes);
+        assert_eq!(1, profile.multibyte.calls);
+        assert_eq!(1, profile.multibyte.bytes);
+    } else {
+        assert_eq!(0, profile.ascii.calls);
+        assert_eq!(3, profile.multibyte.calls);
+        assert_eq!(8, profile.multibyte.bytes);
+    }
+
+    assert_eq!("abc中defg", builder.as_valid_str());
+
+    builder.push("中".as_bytes()[0]).unwrap();
+    builder.push("中".as_bytes()[1]).unwrap();
+    builder.push("中".as_bytes()[2]).unwrap();
+
+    let profile = *builder.validation_profile().unwrap();
+
+    assert_eq!(3, profile.stitch.calls);
+    assert_eq!(4, profile.stitch.bytes);
+
+    builder.push_str_lossy_from_bytes(b"h\xFFi").unwrap();
+
+    let profile = *builder.validation_profile().unwrap();
+
+    assert_eq!("abc中defg中h\u{FFFD}i", builder.as_valid_str());
+
+    if cfg!(all(feature = "sse2", target_arch = "x86_64")) {
+        assert_eq!(5, profile.ascii.calls);
+        assert_eq!(9, profile.ascii.bytes);
+    } else {
+        assert_eq!(6, profile.multibyte.calls);
+    }
+
+    builder.set_validation_profiling(false);
+
+    assert!(builder.validation_profile().is_none());
+}
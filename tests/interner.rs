@@ -0,0 +1,28 @@
+#![cfg(feature = "lasso")]
+
+use lasso::Rodeo;
+use utf8_builder::{Utf8Builder, Utf8Error};
+
+#[test]
+fn finalize_interned() {
+    let mut rodeo = Rodeo::default();
+
+    let key = Utf8Builder::from("中文").finalize_interned(&mut rodeo).unwrap();
+
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(&"中文".as_bytes()[..4]).unwrap();
+
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 1, need: 2
+        }),
+        builder.clone().finalize_interned(&mut rodeo)
+    );
+
+    builder.push_chunk(&"中文".as_bytes()[4..]).unwrap();
+
+    assert_eq!(key, builder.finalize_interned(&mut rodeo).unwrap());
+    assert_eq!("中文", rodeo.resolve(&key));
+    assert_eq!(1, rodeo.len());
+}
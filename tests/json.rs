@@ -0,0 +1,24 @@
+use utf8_builder::Utf8Builder;
+
+#[test]
+fn finalize_json_escaped() {
+    let builder = Utf8Builder::from("say \"你好\"\\\n\t\u{1}\u{8}\u{C}\r😀");
+
+    assert_eq!(
+        "say \\\"你好\\\"\\\\\\n\\t\\u0001\\b\\f\\r😀",
+        builder.finalize_json_escaped().unwrap()
+    );
+
+    assert!(Utf8Builder::try_from_partial(b"a\"\xE4").unwrap().finalize_json_escaped().is_err());
+}
+
+#[test]
+fn write_json_escaped() {
+    let builder = Utf8Builder::try_from_partial(b"a\"\xE4").unwrap();
+
+    let mut s = String::new();
+
+    builder.write_json_escaped(&mut s).unwrap();
+
+    assert_eq!("a\\\"", s);
+}
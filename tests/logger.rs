@@ -0,0 +1,29 @@
+#![cfg(feature = "log")]
+
+use log::{Level, Log, Record};
+use utf8_builder::Utf8Logger;
+
+fn log(logger: &Utf8Logger, level: Level, message: &str) {
+    logger.log(
+        &Record::builder().level(level).target("app").args(format_args!("{}", message)).build(),
+    );
+}
+
+#[test]
+fn utf8_logger() {
+    let logger = Utf8Logger::new(40);
+
+    log(&logger, Level::Info, "啟動");
+    log(&logger, Level::Warn, "low memory");
+
+    assert_eq!("INFO app: 啟動\nWARN app: low memory\n", logger.dump());
+
+    log(&logger, Level::Error, "fault");
+
+    assert_eq!("WARN app: low memory\nERROR app: fault\n", logger.take());
+    assert_eq!("", logger.dump());
+
+    log(&logger, Level::Error, "a very long message which does not fit");
+
+    assert_eq!("ERROR app: a very long message which do\n", logger.dump());
+}
@@ -0,0 +1,37 @@
+use utf8_builder::{MatchCounter, Utf8Builder};
+
+const TEXT: &str = "ERROR a\nERRORERROR 錯誤\nERRO錯誤R\n錯誤錯誤";
+
+#[test]
+fn count_matches() {
+    for chunk_size in 1..=8 {
+        let mut tee = Utf8Builder::new().tee(MatchCounter::new("ERROR"));
+
+        for chunk in TEXT.as_bytes().chunks(chunk_size) {
+            tee.push_chunk(chunk).unwrap();
+        }
+
+        assert_eq!(3, tee.sink().count_matches());
+
+        let mut tee = Utf8Builder::new().tee(MatchCounter::new("錯誤"));
+
+        for chunk in TEXT.as_bytes().chunks(chunk_size) {
+            tee.push_chunk(chunk).unwrap();
+        }
+
+        assert_eq!(TEXT.matches("錯誤").count() as u64, tee.sink().count_matches());
+    }
+
+    let mut counter = MatchCounter::new("aa");
+
+    counter.record("a");
+    counter.record("aaa");
+
+    assert_eq!(2, counter.count_matches());
+
+    let mut counter = MatchCounter::new("");
+
+    counter.record("abc");
+
+    assert_eq!(0, counter.count_matches());
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "no-panic")]
+
+use utf8_builder::{Utf8Builder, Utf8Error};
+
+#[test]
+fn no_panic() {
+    let mut builder = Utf8Builder::from("中文");
+
+    assert_eq!(Err(Utf8Error::AllocationFailed), builder.try_reserve(usize::MAX));
+    assert_eq!(Err(Utf8Error::Invalid), builder.extend_from_within(1..3));
+    assert_eq!(Err(Utf8Error::Invalid), builder.extend_from_within(3..7));
+
+    builder.extend_from_within(..3).unwrap();
+    builder.try_reserve(4).unwrap();
+
+    assert!(builder.capacity() >= 13);
+    assert_eq!("中文中", builder.finalize().unwrap());
+}
+
+#[test]
+fn no_panic_push() {
+    let mut builder = Utf8Builder::from("中文");
+
+    // a failed allocation does not poison the builder
+    assert_eq!(Err(Utf8Error::AllocationFailed), builder.push_repeat_char('a', usize::MAX / 2));
+    assert!(builder.error().is_none());
+
+    builder.push_str("ab").unwrap();
+
+    assert_eq!("中文ab", builder.finalize().unwrap());
+}
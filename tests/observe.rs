@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+use std::time::Duration;
+
+use utf8_builder::{PushStats, ThroughputPolicy, Utf8Builder, Utf8Error};
+
+#[test]
+fn observe() {
+    let mut stats = Vec::new();
+
+    let mut builder = Utf8Builder::new().observe(|s: &PushStats| {
+        stats.push((s.bytes, s.chars, s.total_bytes, s.total_chars));
+
+        Ok(())
+    });
+
+    builder.push_chunk(&"中文".as_bytes()[..4]).unwrap();
+    builder.push_chunk(&"中文".as_bytes()[4..]).unwrap();
+    builder.push_str("ab").unwrap();
+    builder.push_char('字').unwrap();
+    builder.push(b'c').unwrap();
+
+    drop(builder);
+
+    assert_eq!(vec![(4, 1, 4, 1), (2, 1, 6, 2), (2, 2, 8, 4), (3, 1, 11, 5), (1, 1, 12, 6)], stats);
+}
+
+#[test]
+fn throughput_policy() {
+    let mut builder =
+        Utf8Builder::new().observe(ThroughputPolicy::new(1_000_000, Duration::from_millis(10)));
+
+    builder.push_str("a").unwrap();
+
+    std::thread::sleep(Duration::from_millis(20));
+
+    assert_eq!(Err(Utf8Error::PolicyViolated), builder.push_str("b"));
+    assert_eq!(Err(Utf8Error::PolicyViolated), builder.push_str("c"));
+    assert_eq!(Err(Utf8Error::PolicyViolated), builder.finalize());
+
+    let mut builder = Utf8Builder::new()
+        .observe(ThroughputPolicy::new(0, Duration::ZERO).max_duration(Duration::from_millis(10)));
+
+    builder.push_str("a").unwrap();
+
+    std::thread::sleep(Duration::from_millis(20));
+
+    assert_eq!(Err(Utf8Error::PolicyViolated), builder.push_str("b"));
+}
+
+#[test]
+fn observe_rejected_push() {
+    let mut builder = Utf8Builder::new().observe(|s: &PushStats| {
+        if s.total_bytes > 4 {
+            Err(Utf8Error::PolicyViolated)
+        } else {
+            Ok(())
+        }
+    });
+
+    builder.push_chunk(&"中文".as_bytes()[..4]).unwrap();
+
+    assert_eq!(Err(Utf8Error::PolicyViolated), builder.push_chunk(&"中文".as_bytes()[4..]));
+    assert_eq!(Err(Utf8Error::PolicyViolated), builder.push_str("a"));
+
+    let (builder, _) = builder.into_parts();
+
+    assert_eq!(Utf8Error::PolicyViolated, builder.error().unwrap().kind);
+    assert_eq!("中", builder.as_valid_str());
+    assert_eq!(4, builder.len());
+}
@@ -0,0 +1,38 @@
+use utf8_builder::{AsciiSet, PercentEncodeSink, Utf8Builder};
+
+const PATH_SEGMENT: AsciiSet = AsciiSet::CONTROLS.add(b' ').add(b'/').add(b'?').add(b'#');
+
+#[test]
+fn finalize_percent_encoded() {
+    let builder = Utf8Builder::from("a b/中?#\n~");
+
+    assert_eq!(
+        "a%20b%2F%E4%B8%AD%3F%23%0A~",
+        builder.finalize_percent_encoded(&PATH_SEGMENT).unwrap()
+    );
+
+    let builder = Utf8Builder::from("a-Z_9.");
+
+    assert_eq!(
+        "a%2DZ%5F9.",
+        builder.finalize_percent_encoded(&AsciiSet::NON_ALPHANUMERIC.remove(b'.')).unwrap()
+    );
+
+    let builder = Utf8Builder::try_from_partial(b"a\xE4").unwrap();
+
+    assert!(builder.finalize_percent_encoded(&AsciiSet::EMPTY).is_err());
+}
+
+#[test]
+fn percent_encode_sink() {
+    let mut tee = Utf8Builder::new()
+        .tee(PercentEncodeSink {
+            writer: String::new(), set: PATH_SEGMENT
+        });
+
+    for chunk in "中 文".as_bytes().chunks(2) {
+        tee.push_chunk(chunk).unwrap();
+    }
+
+    assert_eq!("%E4%B8%AD%20%E6%96%87", tee.sink().writer);
+}
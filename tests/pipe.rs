@@ -0,0 +1,65 @@
+#![cfg(feature = "std")]
+
+use utf8_builder::{FinalizeError, PipeStats, Utf8Error, Utf8Pipe};
+
+#[test]
+fn pipe() {
+    let data = "ab\n中文\n😀".as_bytes();
+
+    for buffer_size in 1..=data.len() {
+        let pipe = Utf8Pipe {
+            buffer_size,
+            ..Utf8Pipe::default()
+        };
+
+        let mut output = Vec::new();
+
+        assert_eq!(
+            PipeStats {
+                bytes: 14, chars: 7, lines: 2, replaced: 0
+            },
+            pipe.pipe(data, &mut output).unwrap()
+        );
+        assert_eq!(data, output.as_slice());
+    }
+
+    let mut output = Vec::new();
+
+    assert!(matches!(
+        Utf8Pipe::new(false).pipe(&b"ab\n\xE4\xB8a"[..], &mut output),
+        Err(FinalizeError::Utf8(Utf8Error::Invalid))
+    ));
+    assert_eq!(b"ab\n", output.as_slice());
+
+    let mut output = Vec::new();
+
+    assert!(matches!(
+        Utf8Pipe::new(false).pipe(&b"ab\xE4\xB8"[..], &mut output),
+        Err(FinalizeError::Utf8(Utf8Error::Incomplete {
+            have: 2, need: 1
+        }))
+    ));
+    assert_eq!(b"ab", output.as_slice());
+}
+
+#[test]
+fn pipe_lossy() {
+    let data = b"a\xFFb\n\xE4\xB8c\xE4\xB8";
+
+    for buffer_size in 1..=data.len() {
+        let pipe = Utf8Pipe {
+            lossy: true,
+            buffer_size,
+        };
+
+        let mut output = Vec::new();
+
+        assert_eq!(
+            PipeStats {
+                bytes: 13, chars: 7, lines: 1, replaced: 3
+            },
+            pipe.pipe(&data[..], &mut output).unwrap()
+        );
+        assert_eq!("a\u{FFFD}b\n\u{FFFD}c\u{FFFD}", String::from_utf8(output).unwrap());
+    }
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "std")]
+
+use utf8_builder::{PipelinedBuilder, Utf8Error};
+
+const TEXT: &str = "This is English. 這是中文。😀 😃 😄";
+
+#[test]
+fn pipelined_builder() {
+    for chunk_size in 1..=8 {
+        let mut builder = PipelinedBuilder::new(2);
+
+        for chunk in TEXT.as_bytes().chunks(chunk_size) {
+            builder.push_chunk(chunk).unwrap();
+        }
+
+        assert_eq!(TEXT, builder.finalize().unwrap());
+    }
+
+    let mut builder = PipelinedBuilder::new(1);
+
+    builder.push_chunk(&b"\xE4\xB8"[..]).unwrap();
+
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 2, need: 1
+        }),
+        builder.finalize()
+    );
+
+    let mut builder = PipelinedBuilder::new(1);
+
+    builder.push_chunk(&b"\xFF"[..]).unwrap();
+
+    let err = loop {
+        if let Err(err) = builder.push_chunk(&b"a"[..]) {
+            break err;
+        }
+    };
+
+    assert_eq!(Utf8Error::Invalid, err);
+    assert_eq!(Err(Utf8Error::Invalid), builder.try_push_chunk(&b"a"[..]));
+    assert_eq!(Err(Utf8Error::Invalid), builder.finalize());
+}
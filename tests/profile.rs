@@ -0,0 +1,86 @@
+use utf8_builder::{BomPolicy, Profile, Utf8Builder, Utf8Error};
+
+#[test]
+fn strict() {
+    let mut builder = Utf8Builder::new();
+
+    builder.set_profile(Profile::Strict);
+    builder.push_str("\u{FEFF}a\tb\r\n中").unwrap();
+
+    for s in &["\u{FEFF}", "\u{0}", "\u{7F}", "\u{85}", "\u{FDD0}", "\u{FFFE}", "\u{10FFFF}"] {
+        let mut builder = builder.clone();
+
+        for chunk_size in 1..=4 {
+            let mut builder = builder.clone();
+
+            let bytes = format!("x{}y", s).into_bytes();
+
+            assert!(bytes
+                .chunks(chunk_size)
+                .try_for_each(|c| builder.push_chunk(c).map(|_| ()))
+                .is_err());
+            assert_eq!("\u{FEFF}a\tb\r\n中x", builder.as_valid_str());
+            assert!(builder.is_valid());
+        }
+
+        assert!(builder.push_str(s).is_err());
+    }
+
+    assert_eq!("\u{FEFF}a\tb\r\n中", builder.finalize().unwrap());
+}
+
+#[test]
+fn identifier() {
+    let mut builder = Utf8Builder::new();
+
+    builder.set_profile(Profile::Identifier);
+    builder.push_str("名前_1").unwrap();
+
+    assert!(builder.clone().push_char(' ').is_err());
+    assert!(builder.clone().push(b'\n').is_err());
+    assert!(builder.clone().push_char('\u{FEFF}').is_err());
+    assert_eq!("名前_1", builder.finalize().unwrap());
+
+    let mut builder = Utf8Builder::new();
+
+    assert_eq!(Profile::Freeform, builder.profile());
+
+    builder.push_str("\u{0}\u{FFFF}").unwrap();
+}
+
+#[test]
+fn bom_policy() {
+    let input = "\u{FEFF}a\u{FEFF}b中\u{FEFF}\u{FEFF}c".as_bytes();
+
+    for chunk_size in 1..=4 {
+        let mut builder = Utf8Builder::new();
+
+        assert_eq!(BomPolicy::Keep, builder.bom_policy());
+
+        builder.set_bom_policy(BomPolicy::Strip);
+
+        for chunk in input.chunks(chunk_size) {
+            builder.push_chunk(chunk).unwrap();
+        }
+
+        assert_eq!("\u{FEFF}ab中c", builder.finalize().unwrap());
+
+        let mut builder = Utf8Builder::new();
+
+        builder.set_bom_policy(BomPolicy::Reject);
+
+        assert_eq!(
+            Err(Utf8Error::Invalid),
+            input.chunks(chunk_size).try_for_each(|c| builder.push_chunk(c).map(|_| ()))
+        );
+        assert_eq!("\u{FEFF}a", builder.as_valid_str());
+    }
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_bom_policy(BomPolicy::Strip);
+    builder.set_profile(Profile::Strict);
+    builder.push_str("a\u{FEFF}b").unwrap();
+
+    assert_eq!("ab", builder.finalize().unwrap());
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "std")]
+
+use std::io::{self, Read};
+
+use utf8_builder::Utf8Builder;
+
+#[test]
+fn bytes_reader() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(&"ab中文".as_bytes()[..7]).unwrap();
+
+    let mut buf = [0; 4];
+
+    let mut reader = builder.bytes_reader();
+
+    assert_eq!(4, reader.read(&mut buf).unwrap());
+    assert_eq!(b"ab\xE4\xB8", &buf);
+    assert_eq!(1, reader.read(&mut buf).unwrap());
+    assert_eq!(b"\xAD", &buf[..1]);
+    assert_eq!(0, reader.read(&mut buf).unwrap());
+
+    drop(reader);
+
+    assert_eq!("", builder.as_valid_str());
+    assert_eq!(2, builder.len());
+
+    builder.push_chunk(&"文".as_bytes()[2..]).unwrap();
+
+    let mut output = Vec::new();
+
+    io::copy(&mut builder.bytes_reader(), &mut output).unwrap();
+
+    assert_eq!("文".as_bytes(), output);
+    assert!(builder.is_empty());
+}
+
+#[test]
+fn bytes_reader_small_reads() {
+    let text = "This is English. 這是中文。😀".repeat(100);
+
+    let mut builder = Utf8Builder::from(text.as_str());
+
+    let mut output = Vec::new();
+
+    {
+        let mut reader = builder.bytes_reader();
+        let mut buf = [0; 3];
+
+        for _ in 0..500 {
+            let n = reader.read(&mut buf).unwrap();
+
+            output.extend_from_slice(&buf[..n]);
+        }
+    }
+
+    assert_eq!(text.as_bytes()[..1500], output[..]);
+
+    // the characters which have been read are removed when the reader is dropped
+    let end = (0..=1500).rev().find(|&i| text.is_char_boundary(i)).unwrap();
+
+    assert_eq!(&text[end..], builder.as_valid_str());
+}
+
+#[test]
+fn bytes_reader_dropped_in_character() {
+    let mut builder = Utf8Builder::from("中a");
+
+    let mut buf = [0; 2];
+
+    assert_eq!(2, builder.bytes_reader().read(&mut buf).unwrap());
+    assert_eq!("中a", builder.finalize().unwrap());
+}
+
+#[test]
+fn from_reader_until() {
+    let data = "ab\r\n中文\r\r\n😀".as_bytes();
+
+    for capacity in 1..=data.len() {
+        let mut reader = io::BufReader::with_capacity(capacity, data);
+
+        assert_eq!("ab", Utf8Builder::from_reader_until(&mut reader, b"\r\n", false).unwrap());
+        assert_eq!(
+            "中文\r\r\n",
+            Utf8Builder::from_reader_until(&mut reader, b"\r\n", true).unwrap()
+        );
+        assert_eq!("😀", Utf8Builder::from_reader_until(&mut reader, b"\r\n", true).unwrap());
+        assert_eq!("", Utf8Builder::from_reader_until(&mut reader, b"\r\n", true).unwrap());
+    }
+
+    let mut reader: &[u8] = b"aaab|\xE4\xB8|c";
+
+    assert_eq!("a", Utf8Builder::from_reader_until(&mut reader, b"aab", false).unwrap());
+    assert_eq!(b"|\xE4\xB8|c", reader);
+
+    assert_eq!("|", Utf8Builder::from_reader_until(&mut reader, b"|", true).unwrap());
+
+    let err = Utf8Builder::from_reader_until(&mut reader, b"|", false).unwrap_err();
+
+    assert_eq!(io::ErrorKind::InvalidData, err.kind());
+}
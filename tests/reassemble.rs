@@ -0,0 +1,74 @@
+use utf8_builder::{Utf8Error, Utf8Reassembler};
+
+#[test]
+fn reassemble() {
+    let text = "This is English. 這是中文。😀";
+    let bytes = text.as_bytes();
+
+    let chunks: Vec<(usize, &[u8])> =
+        (0..bytes.len()).step_by(5).map(|i| (i, &bytes[i..(i + 5).min(bytes.len())])).collect();
+
+    let mut reassembler = Utf8Reassembler::new();
+
+    for &(offset, chunk) in chunks.iter().rev() {
+        reassembler.insert(offset, chunk).unwrap();
+
+        assert_eq!(offset == 0, reassembler.is_complete());
+    }
+
+    assert!(reassembler.is_complete());
+    assert_eq!(text, reassembler.finalize().unwrap());
+
+    let mut reassembler = Utf8Reassembler::new();
+
+    for &(offset, chunk) in chunks.iter().step_by(2).chain(chunks.iter().skip(1).step_by(2)) {
+        reassembler.insert(offset, chunk).unwrap();
+    }
+
+    // overlapping and duplicated chunks
+    reassembler.insert(3, &bytes[3..20]).unwrap();
+    reassembler.insert(0, bytes).unwrap();
+
+    assert_eq!(text, reassembler.finalize().unwrap());
+
+    let mut reassembler = Utf8Reassembler::new();
+
+    reassembler.insert(6, &bytes[6..10]).unwrap();
+
+    assert_eq!(Some(0), reassembler.first_gap());
+    assert_eq!(Err(Utf8Error::Invalid), reassembler.insert(20, b"\x80\x80\x80\x80"));
+    assert_eq!(Err(Utf8Error::Invalid), reassembler.insert(20, b"a\xFFb"));
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 0, need: 6
+        }),
+        reassembler.clone().finalize()
+    );
+
+    reassembler.insert(0, &bytes[..6]).unwrap();
+
+    assert_eq!(None, reassembler.first_gap());
+    assert_eq!("This is En", reassembler.finalize().unwrap());
+}
+
+#[test]
+fn reassemble_conflicting_overlap() {
+    let mut reassembler = Utf8Reassembler::new();
+
+    // the bytes which arrive first win, and a longer chunk only adds the bytes beyond them
+    reassembler.insert(2, b"cd").unwrap();
+    reassembler.insert(2, b"XYef").unwrap();
+    reassembler.insert(3, b"ZZZg").unwrap();
+    reassembler.insert(0, b"ab").unwrap();
+
+    assert_eq!("abcdefg", reassembler.finalize().unwrap());
+
+    // an earlier fragment at a higher offset is not overwritten by a later one at a lower offset
+    let mut reassembler = Utf8Reassembler::new();
+
+    reassembler.insert(3, b"ZZZg").unwrap();
+    reassembler.insert(2, b"cdef").unwrap();
+    reassembler.insert(0, b"abXYZ").unwrap();
+
+    assert_eq!("abcZZZg", reassembler.finalize().unwrap());
+}
@@ -0,0 +1,59 @@
+use utf8_builder::{Segment, Utf8Builder};
+
+#[test]
+fn error_recovery_iterator() {
+    let mut builder = Utf8Builder::new();
+
+    let recovery = builder.error_recovery_iterator("a中".as_bytes());
+
+    assert!(recovery.orphaned().is_empty());
+    assert_eq!(0, recovery.count());
+
+    let recovery = builder.error_recovery_iterator(b"b\xFF\xFEc\xE4\xB8d\xF0\x9F");
+
+    assert_eq!("a中b", builder.as_valid_str());
+    assert_eq!(
+        vec![
+            Segment::Invalid(b"\xFF"),
+            Segment::Invalid(b"\xFE"),
+            Segment::Valid("c"),
+            Segment::Invalid(b"\xE4\xB8"),
+            Segment::Valid("d"),
+            Segment::Incomplete(b"\xF0\x9F"),
+        ],
+        recovery.collect::<Vec<_>>()
+    );
+
+    assert!(builder.error().is_none());
+
+    builder.push_chunk(b"\xE4").unwrap();
+
+    let recovery = builder.error_recovery_iterator(b"\xB8e");
+
+    assert_eq!(b"\xE4", recovery.orphaned());
+    assert_eq!(vec![Segment::Invalid(b"\xB8"), Segment::Valid("e")], recovery.collect::<Vec<_>>());
+    assert!(builder.is_valid());
+
+    builder.set_limit(Some(builder.len() + 1));
+
+    let recovery = builder.error_recovery_iterator(b"fg\xFF");
+
+    assert_eq!(b"fg\xFF", recovery.as_bytes());
+    assert_eq!("a中b", builder.finalize().unwrap());
+}
+
+#[test]
+fn error_recovery_iterator_empty_chunk() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(b"a\xE4").unwrap();
+
+    let recovery = builder.error_recovery_iterator(b"");
+
+    assert_eq!(b"", recovery.orphaned());
+    assert_eq!(0, recovery.count());
+
+    builder.push_chunk(b"\xB8\xAD").unwrap();
+
+    assert_eq!("a中", builder.finalize().unwrap());
+}
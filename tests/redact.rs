@@ -0,0 +1,28 @@
+use utf8_builder::Utf8Redactor;
+
+#[test]
+fn redactor() {
+    let text = "key=sk-12345, 密碼=秘密 sk-1 and sk-12345sk-12345";
+
+    for chunk_size in 1..=8 {
+        let mut redactor = Utf8Redactor::new("***");
+
+        redactor.add_pattern("sk-12345").add_pattern("秘密");
+
+        for chunk in text.as_bytes().chunks(chunk_size) {
+            redactor.push_chunk(chunk).unwrap();
+
+            assert!(!redactor.as_redacted_str().contains("sk-12345"));
+        }
+
+        assert_eq!("key=***, 密碼=*** sk-1 and ******", redactor.finalize().unwrap());
+    }
+
+    let mut redactor = Utf8Redactor::new("***");
+
+    redactor.add_pattern("secret");
+    redactor.push_str("a secr").unwrap();
+
+    assert_eq!("a ", redactor.as_redacted_str());
+    assert_eq!("a secr", redactor.finalize().unwrap());
+}
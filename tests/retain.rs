@@ -0,0 +1,47 @@
+use utf8_builder::Utf8Builder;
+
+#[test]
+fn retain_lines() {
+    let data = "ERROR 中\r\ninfo\nERROR 😀\n\nERROR end".as_bytes();
+
+    for size in 1..=data.len() {
+        let mut judged = Vec::new();
+
+        let mut builder = Utf8Builder::from("kept\nERROR: ").retain_lines(|line: &str| {
+            judged.push(line.to_string());
+
+            line.starts_with("ERROR")
+        });
+
+        for chunk in data.chunks(size) {
+            builder.push_chunk(chunk).unwrap();
+
+            assert!(!builder.builder().as_valid_str().contains("info\n"));
+        }
+
+        assert_eq!("kept\nERROR: ERROR 中\r\nERROR 😀\nERROR end", builder.finalize().unwrap());
+        assert_eq!(vec!["ERROR: ERROR 中", "info", "ERROR 😀", "", "ERROR end"], judged);
+    }
+
+    let mut builder = Utf8Builder::new().retain_lines(|line: &str| !line.is_empty());
+
+    builder.push_str("a\n\n").unwrap();
+    builder.push_char('\n').unwrap();
+    builder.push(b'b').unwrap();
+
+    assert_eq!("a\nb", builder.finalize().unwrap());
+}
+
+#[test]
+fn retain_lines_compaction() {
+    let mut builder = Utf8Builder::new().retain_lines(|line: &str| line.len() % 2 == 0);
+
+    builder.push_chunk(b"a\nbb\nccc\ndddd\ne\nx\xE6").unwrap();
+
+    assert_eq!("bb\ndddd\nx", builder.builder().as_valid_str());
+    assert_eq!(10, builder.builder().len());
+
+    builder.push_chunk(b"\x96\x87\nff\n").unwrap();
+
+    assert_eq!("bb\ndddd\nx文\nff\n", builder.finalize().unwrap());
+}
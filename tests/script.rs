@@ -0,0 +1,21 @@
+#![cfg(feature = "script")]
+
+use utf8_builder::{Script, ScriptTally, Utf8Builder};
+
+#[test]
+fn script_tally() {
+    let mut tee = Utf8Builder::new().tee(ScriptTally::new());
+
+    for chunk in "Hello, 世界! Привет".as_bytes().chunks(3) {
+        tee.push_chunk(chunk).unwrap();
+    }
+
+    let tally = tee.sink();
+
+    assert_eq!(5, tally.count(Script::Latin));
+    assert_eq!(2, tally.count(Script::Han));
+    assert_eq!(6, tally.count(Script::Cyrillic));
+    assert_eq!(4, tally.count(Script::Common));
+    assert_eq!(0, tally.count(Script::Arabic));
+    assert_eq!(Some(Script::Cyrillic), tally.dominant());
+}
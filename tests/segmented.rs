@@ -0,0 +1,60 @@
+use utf8_builder::{SegmentedBuilder, Utf8Error};
+
+#[test]
+fn segmented_builder() {
+    let text = "This is English. 這是中文。😀";
+
+    for chunk_size in 1..=5 {
+        let mut builder = SegmentedBuilder::with_segment_size(text.len(), 4);
+
+        for chunk in text.as_bytes().chunks(chunk_size) {
+            builder.push_chunk(chunk).unwrap();
+        }
+
+        assert_eq!(text.len(), builder.len());
+
+        let s = builder.finalize().unwrap();
+
+        assert_eq!(text, s);
+        assert_eq!(text.len(), s.capacity());
+    }
+
+    let mut builder = SegmentedBuilder::new(4);
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), builder.push_chunk(b"abcde"));
+
+    builder.push_chunk(&"中".as_bytes()[..2]).unwrap();
+
+    assert!(!builder.is_valid());
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 2, need: 2
+        }),
+        builder.clone().finalize()
+    );
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk(b"ab"));
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk(b"\xAD"));
+    assert_eq!(Err(Utf8Error::Invalid), builder.finalize());
+}
+
+#[test]
+fn segmented_builder_truncated() {
+    let mut builder = SegmentedBuilder::new(1000);
+
+    builder.push_chunk(b"abc").unwrap();
+
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 0, need: 255
+        }),
+        builder.finalize()
+    );
+
+    let mut builder = SegmentedBuilder::new(3);
+
+    builder.push_chunk(&"中".as_bytes()[..2]).unwrap();
+    builder.push_chunk(b"\xAD").unwrap();
+
+    assert_eq!("中", builder.finalize().unwrap());
+}
@@ -0,0 +1,23 @@
+use utf8_builder::SseSplitter;
+
+const STREAM: &str =
+    "\u{FEFF}data: 你好\n\nevent: ping\r\ndata: 1\r\n\r\n\n\ndata: 😀\r\rdata: tail";
+
+#[test]
+fn sse_splitter() {
+    for chunk_size in 1..=8 {
+        let mut splitter = SseSplitter::new();
+        let mut events = Vec::new();
+
+        for chunk in STREAM.as_bytes().chunks(chunk_size) {
+            splitter.push_chunk(chunk).unwrap();
+
+            while let Some(event) = splitter.next_event() {
+                events.push(event);
+            }
+        }
+
+        assert_eq!(["data: 你好", "event: ping\r\ndata: 1", "data: 😀"], events.as_slice());
+        assert_eq!("data: tail", splitter.finalize().unwrap());
+    }
+}
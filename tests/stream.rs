@@ -0,0 +1,32 @@
+#![cfg(feature = "stream")]
+
+use futures::{executor::block_on, stream, StreamExt};
+use utf8_builder::{Utf8Error, Utf8Stream};
+
+const TEXT: &str = "This is English. 這是中文。😀 😃 😄";
+
+#[test]
+fn utf8_stream() {
+    for chunk_size in 1..=8 {
+        let chunks: Vec<&[u8]> = TEXT.as_bytes().chunks(chunk_size).collect();
+
+        let items: Vec<String> =
+            block_on(Utf8Stream::new(stream::iter(chunks)).map(Result::unwrap).collect());
+
+        assert!(items.iter().all(|s| !s.is_empty()));
+        assert_eq!(TEXT, items.concat());
+    }
+
+    let chunks: Vec<&[u8]> = vec![b"ab", b"\xE4\xB8"];
+
+    let items: Vec<_> = block_on(Utf8Stream::new(stream::iter(chunks)).collect());
+
+    assert_eq!(2, items.len());
+    assert_eq!("ab", items[0].as_ref().unwrap());
+    assert_eq!(
+        &Err(Utf8Error::Incomplete {
+            have: 2, need: 1
+        }),
+        &items[1]
+    );
+}
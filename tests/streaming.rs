@@ -0,0 +1,44 @@
+use std::num::NonZeroUsize;
+
+use utf8_builder::{StreamingError, Utf8Builder};
+
+#[test]
+fn check_complete() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(&"😀".as_bytes()[..1]).unwrap();
+
+    assert_eq!(
+        Err(StreamingError::Incomplete(NonZeroUsize::new(3).unwrap())),
+        builder.check_complete()
+    );
+
+    builder.push_chunk(&"😀".as_bytes()[1..]).unwrap();
+
+    assert_eq!(Ok(()), builder.check_complete());
+}
+
+#[test]
+fn validate_streaming() {
+    assert_eq!(Ok("中"), utf8_builder::validate_streaming("中".as_bytes()));
+    assert_eq!(
+        Err(StreamingError::Incomplete(NonZeroUsize::new(1).unwrap())),
+        utf8_builder::validate_streaming(&"中".as_bytes()[..2])
+    );
+    assert_eq!(Err(StreamingError::Invalid), utf8_builder::validate_streaming(b"\xE4a"));
+}
+
+#[cfg(feature = "nom")]
+#[test]
+fn into_nom() {
+    use nom::{error::Error, Err, Needed};
+
+    let err: Err<Error<&[u8]>> =
+        StreamingError::Incomplete(NonZeroUsize::new(2).unwrap()).into_nom(&b""[..]);
+
+    assert_eq!(Err::Incomplete(Needed::new(2)), err);
+
+    let err: Err<Error<&[u8]>> = StreamingError::Invalid.into_nom(&b"\xFF"[..]);
+
+    assert!(matches!(err, Err::Error(_)));
+}
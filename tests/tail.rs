@@ -0,0 +1,38 @@
+use utf8_builder::{TailBuilder, Utf8Error};
+
+const TEXT: &str = "This is English. 這是中文。😀 😃 😄";
+
+#[test]
+fn tail_builder() {
+    for chunk_size in 1..=TEXT.len() {
+        let mut builder = TailBuilder::new(10);
+
+        for chunk in TEXT.as_bytes().chunks(chunk_size) {
+            builder.push_chunk(chunk).unwrap();
+
+            assert!(builder.as_valid_str().len() <= 10);
+        }
+
+        assert_eq!(TEXT.len() as u64, builder.total_len());
+        assert_eq!(" 😃 😄", builder.finalize().unwrap());
+    }
+
+    let mut builder = TailBuilder::new(4);
+
+    builder.push_str("abcdef").unwrap();
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk(b"gh\xFFijklmn"));
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_str("o"));
+    assert_eq!(8, builder.total_len());
+    assert_eq!("efgh", builder.as_valid_str());
+
+    let mut builder = TailBuilder::new(2);
+
+    builder.push_chunk(&"中".as_bytes()[..2]).unwrap();
+
+    assert!(!builder.is_valid());
+
+    builder.push_chunk(&"中".as_bytes()[2..]).unwrap();
+
+    assert_eq!("", builder.finalize().unwrap());
+}
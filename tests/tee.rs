@@ -0,0 +1,41 @@
+use utf8_builder::{FmtSink, TeeError, Utf8Builder};
+
+#[test]
+fn tee_fmt() {
+    let text = "Hello, 世界! 😀";
+
+    for chunk_size in 1..=8 {
+        let mut tee = Utf8Builder::new().tee(FmtSink(String::new()));
+
+        for chunk in text.as_bytes().chunks(chunk_size) {
+            tee.push_chunk(chunk).unwrap();
+
+            assert_eq!(tee.builder().as_valid_str(), tee.sink().0);
+        }
+
+        let (builder, sink) = tee.into_parts();
+
+        assert_eq!(text, sink.0);
+        assert_eq!(text, builder.finalize().unwrap());
+    }
+
+    let mut tee = Utf8Builder::new().tee(FmtSink(String::new()));
+
+    tee.push_char('中').unwrap();
+
+    assert!(matches!(tee.push_chunk(b"ab\xFF"), Err(TeeError::Utf8(_))));
+    assert_eq!("中ab", tee.sink().0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn tee_io() {
+    use utf8_builder::IoSink;
+
+    let mut tee = Utf8Builder::new().tee(IoSink(Vec::new()));
+
+    tee.push_str("中").unwrap();
+    tee.push(b'a').unwrap();
+
+    assert_eq!("中a".as_bytes(), tee.sink().0.as_slice());
+}
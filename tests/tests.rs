@@ -1,4 +1,9 @@
-use utf8_builder::Utf8Builder;
+use std::{rc::Rc, sync::Arc};
+
+use utf8_builder::{
+    CharLen, Diverted, ExtendError, ParseError, PushError, PushOutcome, TryExtend, Utf8Builder,
+    Utf8Error, Utf8State, ValidSplit,
+};
 
 const TEXT1: &str = "This is English. And 這是中文。This is number, 123.";
 const TEXT2: &str = "あれは えんびつですか";
@@ -64,3 +69,1124 @@ fn push_chunk() {
         }
     }
 }
+
+#[test]
+fn take_valid() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk("中文".as_bytes()).unwrap();
+    builder.push_chunk(&"字".as_bytes()[..2]).unwrap();
+
+    assert_eq!("中文", builder.as_valid_str());
+    assert_eq!("中文", builder.take_valid());
+    assert_eq!("", builder.as_valid_str());
+    assert_eq!(2, builder.len());
+
+    builder.push(b'\x97').unwrap();
+
+    assert_eq!("字", builder.take_valid());
+    assert!(builder.is_empty());
+}
+
+#[test]
+fn try_from_partial() {
+    let bytes = "中文".as_bytes();
+
+    let builder = Utf8Builder::try_from_partial(&bytes[..4]).unwrap();
+
+    assert!(!builder.is_valid());
+    assert_eq!("中", builder.as_valid_str());
+
+    let mut builder = Utf8Builder::try_from_partial(&bytes[..3]).unwrap();
+
+    assert!(builder.is_valid());
+
+    builder.push_chunk(&bytes[3..]).unwrap();
+
+    assert_eq!("中文", builder.finalize().unwrap());
+
+    assert!(Utf8Builder::try_from_partial(b"\xFFabc").is_err());
+}
+
+#[test]
+fn invalid() {
+    for &bytes in &[
+        &b"\xE4A"[..],
+        b"\xC0\x80",
+        b"\xE0\x80\x80",
+        b"\xED\xA0\x80",
+        b"\xF4\x90\x80\x80",
+        b"\xF5\x80\x80\x80",
+        b"\x80",
+    ] {
+        let mut builder = Utf8Builder::new();
+
+        assert!(bytes.iter().copied().try_for_each(|b| builder.push(b).map(|_| ())).is_err());
+
+        for chunk_size in 1..=4 {
+            let mut builder = Utf8Builder::new();
+
+            assert!(bytes
+                .chunks(chunk_size)
+                .try_for_each(|c| builder.push_chunk(c).map(|_| ()))
+                .is_err());
+        }
+    }
+
+    let mut builder = Utf8Builder::new();
+
+    assert!(builder.push_chunk(b"ab\xE4\xB8\xADc\xFF").is_err());
+    assert_eq!("ab中c", builder.as_valid_str());
+
+    builder.clear_error();
+
+    assert_eq!("ab中c", builder.finalize().unwrap());
+}
+
+#[test]
+fn poisoned() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(b"ab\xE4\xB8").unwrap();
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk(b"c"));
+    assert_eq!(
+        Some(&PushError {
+            kind: Utf8Error::Invalid, offset: 2, context: None
+        }),
+        builder.error()
+    );
+
+    // the pushes fail even with the data completing the character
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk(b"\xAD"));
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_str("d"));
+    assert_eq!(Err(Utf8Error::Invalid), builder.clone().finalize());
+
+    builder.clear_error();
+
+    assert_eq!(None, builder.error());
+
+    builder.push_chunk(b"\xAD").unwrap();
+
+    assert_eq!("ab中", builder.finalize().unwrap());
+}
+
+#[test]
+fn error_context() {
+    let mut builder = Utf8Builder::new();
+
+    builder.set_error_context(3);
+    builder.push_chunk(b"abcd\xE4\xB8").unwrap();
+
+    assert!(builder.push_chunk(b"efg").is_err());
+
+    let err = builder.error().unwrap();
+
+    assert_eq!(4, err.offset);
+
+    let context = err.context.as_ref().unwrap();
+
+    assert_eq!(b"bcd", context.before.as_slice());
+    assert_eq!(b"\xE4\xB8e", context.after.as_slice());
+    assert_eq!("62 63 64 | E4 B8 65", context.to_hex());
+    assert_eq!("bcd|\u{FFFD}e", context.to_lossy());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_error_context(2);
+    builder.set_profile(utf8_builder::Profile::Identifier);
+
+    assert!(builder.push_str("a b").is_err());
+
+    let context = builder.error().unwrap().context.as_ref().unwrap();
+
+    assert_eq!(b"a", context.before.as_slice());
+    assert_eq!(b" b", context.after.as_slice());
+}
+
+#[test]
+fn validate_chunk() {
+    let bytes = "a中😀".as_bytes();
+
+    let mut state = Utf8State::new();
+
+    assert_eq!(
+        ValidSplit {
+            complete: 1, pending: 2
+        },
+        utf8_builder::validate_chunk(&mut state, &bytes[..3]).unwrap()
+    );
+    assert_eq!(&bytes[1..3], state.pending());
+    assert_eq!(1, state.needed());
+    assert_eq!(
+        ValidSplit {
+            complete: 1, pending: 0
+        },
+        utf8_builder::validate_chunk(&mut state, &bytes[3..4]).unwrap()
+    );
+    assert!(state.is_valid());
+    assert_eq!(
+        ValidSplit {
+            complete: 0, pending: 2
+        },
+        utf8_builder::validate_chunk(&mut state, &bytes[4..6]).unwrap()
+    );
+    assert_eq!(
+        ValidSplit {
+            complete: 0, pending: 1
+        },
+        utf8_builder::validate_chunk(&mut state, &bytes[6..7]).unwrap()
+    );
+    assert_eq!(
+        ValidSplit {
+            complete: 3, pending: 0
+        },
+        utf8_builder::validate_chunk(&mut state, &[bytes[7], b'b', b'c']).unwrap()
+    );
+
+    let mut state = Utf8State::new();
+
+    utf8_builder::validate_chunk(&mut state, &bytes[..2]).unwrap();
+
+    assert!(utf8_builder::validate_chunk(&mut state, b"\xB8a").is_err());
+    assert_eq!(&bytes[1..2], state.pending());
+}
+
+#[test]
+fn validate_and_count() {
+    let bytes = "a中😀".as_bytes();
+
+    assert_eq!(
+        CharLen {
+            bytes: 8, chars: 3, on_boundary: true
+        },
+        utf8_builder::validate_and_count(bytes).unwrap()
+    );
+    assert_eq!(
+        CharLen {
+            bytes: 6, chars: 2, on_boundary: false
+        },
+        utf8_builder::validate_and_count(&bytes[..6]).unwrap()
+    );
+    assert_eq!(
+        CharLen {
+            bytes: 0, chars: 0, on_boundary: true
+        },
+        utf8_builder::validate_and_count(b"").unwrap()
+    );
+    assert_eq!(Err(Utf8Error::Invalid), utf8_builder::validate_and_count(&bytes[2..]));
+}
+
+#[test]
+fn push_chunk_long() {
+    let text = format!("{}{}{}", "a".repeat(100), TEXT2, "b".repeat(33));
+
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(text.as_bytes()).unwrap();
+
+    assert_eq!(text, builder.finalize().unwrap());
+
+    let mut bytes = "a".repeat(64).into_bytes();
+
+    bytes.push(0xFF);
+
+    assert!(Utf8Builder::new().push_chunk(&bytes).is_err());
+
+    for i in 0..48 {
+        let mut bytes = "a".repeat(48).into_bytes();
+
+        bytes[i] = 0xFF;
+
+        let mut builder = Utf8Builder::new();
+
+        assert!(builder.push_chunk(&bytes).is_err());
+        assert_eq!(i, builder.len());
+
+        let text = format!("{}中{}", "a".repeat(i), "b".repeat(48 - i));
+
+        assert_eq!(
+            text,
+            Utf8Builder::try_from_partial(text.as_bytes()).unwrap().finalize().unwrap()
+        );
+    }
+}
+
+#[test]
+fn push_chunk_mixed_runs() {
+    let text = format!("{}中文{}😀é{}", "a".repeat(40), "b".repeat(17), "c".repeat(33));
+    let bytes = text.as_bytes();
+
+    for i in 0..bytes.len() {
+        for &b in &[0xFF, 0x80, b'x'] {
+            let mut corrupted = bytes.to_vec();
+
+            corrupted[i] = b;
+
+            let mut builder = Utf8Builder::new();
+
+            let result = builder.push_chunk(&corrupted);
+
+            match std::str::from_utf8(&corrupted) {
+                Ok(_) => assert!(result.is_ok()),
+                Err(err) => {
+                    assert_eq!(err.error_len().is_some(), result.is_err());
+                    assert_eq!(err.valid_up_to(), builder.as_valid_str().len());
+                },
+            }
+        }
+
+        // a truncated chunk keeps the incomplete character
+        let mut builder = Utf8Builder::new();
+
+        builder.push_chunk(&bytes[..i]).unwrap();
+        builder.push_chunk(&bytes[i..]).unwrap();
+
+        assert_eq!(text, builder.finalize().unwrap());
+    }
+}
+
+#[test]
+fn leak() {
+    let s: &'static str = Utf8Builder::from(TEXT1).leak().unwrap();
+
+    assert_eq!(TEXT1, s);
+
+    assert!(Utf8Builder::try_from_partial(&TEXT2.as_bytes()[..1]).unwrap().leak().is_err());
+}
+
+#[test]
+fn try_into_string() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(&"中文".as_bytes()[..4]).unwrap();
+
+    assert_eq!(None, builder.as_str());
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 1, need: 2
+        }),
+        String::try_from(builder.clone())
+    );
+
+    builder.push_chunk(&"中文".as_bytes()[4..]).unwrap();
+
+    assert_eq!(Some("中文"), builder.as_str());
+
+    let s: String = builder.clone().try_into().unwrap();
+
+    assert_eq!("中文", s);
+
+    assert!(builder.push_chunk(b"\xFF").is_err());
+    assert_eq!(None, builder.as_str());
+}
+
+#[test]
+fn seal() {
+    let mut builder = Utf8Builder::with_capacity(256);
+
+    builder.push_str(TEXT2).unwrap();
+
+    let sealed = builder.seal().unwrap();
+    let cloned = sealed.clone();
+
+    assert_eq!(TEXT2, sealed.as_str());
+    assert_eq!(TEXT2.as_bytes(), cloned.as_bytes());
+    assert_eq!(TEXT2.len(), cloned.len());
+    assert_eq!(TEXT2, Arc::<str>::from(cloned).as_ref());
+
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 1, need: 2
+        }),
+        Utf8Builder::try_from_partial(&TEXT2.as_bytes()[..1]).unwrap().seal()
+    );
+}
+
+#[test]
+fn index() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(&"a中文".as_bytes()[..6]).unwrap();
+
+    assert_eq!("a中", &builder[..]);
+    assert_eq!("中", &builder[1..4]);
+    assert_eq!("a", &builder[..=0]);
+    assert_eq!(Some("中"), builder.get(1..));
+    assert_eq!(None, builder.get(1..6));
+    assert_eq!(None, builder.get(2..));
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_complete_characters() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(&"a中文".as_bytes()[..6]).unwrap();
+
+    let _ = &builder[..5];
+}
+
+#[test]
+fn into_bytes() {
+    let bytes = TEXT2.as_bytes();
+
+    let builder = Utf8Builder::try_from_partial(&bytes[..4]).unwrap();
+
+    let (buffer, state) = builder.into_bytes();
+
+    assert_eq!(&bytes[..4], buffer.as_slice());
+    assert_eq!(&bytes[3..4], state.pending());
+}
+
+#[test]
+fn push_chunk_counted() {
+    for chunk_size in 1..=8 {
+        for &text in TEXTS {
+            let mut builder = Utf8Builder::new();
+            let mut count = 0;
+
+            for c in text.as_bytes().chunks(chunk_size) {
+                count += builder.push_chunk_counted(c).unwrap();
+
+                assert_eq!(count, builder.char_count());
+            }
+
+            assert_eq!(text.chars().count(), count);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn freeze() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_str(TEXT1).unwrap();
+    builder.push_chunk(&TEXT2.as_bytes()[..4]).unwrap();
+
+    let snapshot = builder.freeze();
+
+    assert_eq!(&format!("{}{}", TEXT1, &TEXT2[..3]), &*snapshot);
+    assert_eq!(TEXT1.len() + 4, builder.len());
+
+    builder.push_chunk(&TEXT2.as_bytes()[4..]).unwrap();
+
+    let snapshot2 = builder.freeze();
+
+    drop(snapshot);
+
+    assert_eq!(format!("{}{}", TEXT1, TEXT2), snapshot2.as_str());
+
+    builder.push_str(TEXT3).unwrap();
+
+    assert_eq!(format!("{}{}", TEXT1, TEXT2), snapshot2.as_str());
+    assert_eq!(format!("{}{}{}", TEXT1, TEXT2, TEXT3), builder.finalize().unwrap());
+}
+
+#[test]
+fn as_mut_vec() {
+    let mut builder = Utf8Builder::try_from_partial(&"a-b-中".as_bytes()[..5]).unwrap();
+
+    unsafe {
+        for b in builder.as_mut_vec().iter_mut().filter(|b| **b == b'-') {
+            *b = b'+';
+        }
+    }
+
+    builder.push_chunk(&"中".as_bytes()[1..]).unwrap();
+
+    assert_eq!("a+b+中", builder.finalize().unwrap());
+}
+
+#[test]
+fn with_size_hint() {
+    let builder = Utf8Builder::with_size_hint(usize::MAX, None);
+
+    assert!(builder.capacity() <= 64 * 1024);
+
+    let mut builder = Utf8Builder::with_size_hint(1000, Some(8));
+
+    assert_eq!(Some(8), builder.limit());
+
+    builder.push_str("中文").unwrap();
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), builder.push_chunk("字".as_bytes()));
+    assert_eq!(Err(Utf8Error::LimitExceeded), builder.push_char('字'));
+
+    builder.push(b'a').unwrap();
+    builder.push_chunk(b"b").unwrap();
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), builder.push(b'c'));
+    assert_eq!("中文ab", builder.finalize().unwrap());
+}
+
+#[test]
+fn preview() {
+    let builder = Utf8Builder::try_from_partial(&"中文字".as_bytes()[..8]).unwrap();
+
+    assert_eq!("", builder.preview(0));
+    assert_eq!("中", builder.preview(1));
+    assert_eq!("中文", builder.preview(2));
+    assert_eq!("中文", builder.preview(3));
+}
+
+#[test]
+fn extend_from_within() {
+    let mut builder = Utf8Builder::from("ab中");
+
+    builder.extend_from_within(2..).unwrap();
+    builder.extend_from_within(..=1).unwrap();
+
+    assert_eq!("ab中中ab", builder.as_valid_str());
+
+    builder.push_chunk(&"文".as_bytes()[..1]).unwrap();
+
+    assert!(builder.extend_from_within(..).is_err());
+}
+
+#[test]
+#[should_panic]
+fn extend_from_within_not_on_boundary() {
+    let mut builder = Utf8Builder::from("ab中");
+
+    builder.extend_from_within(3..).unwrap();
+}
+
+#[test]
+fn split_whitespace_stream() {
+    let text = "  Hello 世界,\tthis\u{3000}is   中文 tail";
+
+    for chunk_size in 1..=8 {
+        let mut builder = Utf8Builder::new();
+        let mut tokens = Vec::new();
+
+        for chunk in text.as_bytes().chunks(chunk_size) {
+            builder.push_chunk(chunk).unwrap();
+
+            tokens.extend(builder.split_whitespace_stream());
+        }
+
+        assert_eq!(["Hello", "世界,", "this", "is", "中文"], tokens.as_slice());
+        assert_eq!("tail", builder.finalize().unwrap());
+    }
+}
+
+#[test]
+fn split_terminator_drain() {
+    let text = "a.txt\0中文.md\0\0tail";
+
+    for chunk_size in 1..=8 {
+        let mut builder = Utf8Builder::new();
+        let mut records = Vec::new();
+
+        for chunk in text.as_bytes().chunks(chunk_size) {
+            builder.push_chunk(chunk).unwrap();
+
+            records.extend(builder.split_terminator_drain('\0'));
+        }
+
+        assert_eq!(["a.txt", "中文.md", ""], records.as_slice());
+        assert_eq!("tail", builder.finalize().unwrap());
+    }
+}
+
+#[test]
+fn chunks() {
+    let builder = Utf8Builder::try_from_partial(&"ab中文😀c字".as_bytes()[..15]).unwrap();
+
+    assert_eq!(vec!["ab", "中", "文", "😀", "c"], builder.chunks(3).collect::<Vec<_>>());
+    assert_eq!(vec!["a", "b", "中", "文", "😀", "c"], builder.chunks(1).collect::<Vec<_>>());
+    assert_eq!(vec!["ab中文", "😀c"], builder.chunks(8).collect::<Vec<_>>());
+    assert_eq!(vec!["ab中文😀c"], builder.chunks(100).collect::<Vec<_>>());
+    assert_eq!(0, Utf8Builder::new().chunks(3).count());
+}
+
+#[test]
+fn chunks_size_hint() {
+    for text in ["中", "ab中文😀c", "😀😀a😀", "abcdefg"] {
+        let builder = Utf8Builder::from(text);
+
+        for chunk_size in (1..=10).chain([usize::MAX]) {
+            let chunks = builder.chunks(chunk_size);
+            let (lower, upper) = chunks.size_hint();
+            let count = chunks.count();
+
+            assert!(lower <= count && count <= upper.unwrap(), "{:?} {}", text, chunk_size);
+        }
+    }
+}
+
+#[test]
+fn finalize_segments() {
+    let builder = Utf8Builder::from("ab中文😀c");
+
+    assert_eq!(vec!["ab中文", "😀c"], builder.clone().finalize_segments(8).unwrap());
+    assert_eq!(vec!["ab", "中", "文", "😀", "c"], builder.finalize_segments(3).unwrap());
+    assert!(Utf8Builder::new().finalize_segments(3).unwrap().is_empty());
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 1, need: 2
+        }),
+        Utf8Builder::try_from_partial(b"\xE4").unwrap().finalize_segments(3)
+    );
+}
+
+#[test]
+fn char_boundary() {
+    let builder = Utf8Builder::try_from_partial(&"a中b字".as_bytes()[..6]).unwrap();
+
+    assert!(builder.is_char_boundary(0));
+    assert!(builder.is_char_boundary(1));
+    assert!(!builder.is_char_boundary(2));
+    assert!(builder.is_char_boundary(5));
+    assert!(!builder.is_char_boundary(6));
+
+    assert_eq!(1, builder.floor_char_boundary(3));
+    assert_eq!(4, builder.floor_char_boundary(4));
+    assert_eq!(5, builder.floor_char_boundary(100));
+    assert_eq!(4, builder.ceil_char_boundary(2));
+    assert_eq!(0, builder.ceil_char_boundary(0));
+    assert_eq!(5, builder.ceil_char_boundary(100));
+}
+
+#[test]
+fn merge() {
+    let bytes = "中文😀ab".as_bytes();
+
+    let mut builder = Utf8Builder::new();
+
+    builder.append(Utf8Builder::try_from_partial(&bytes[..4]).unwrap()).unwrap();
+    builder.push_chunk(&bytes[4..8]).unwrap();
+    builder.append(Utf8Builder::new()).unwrap();
+    assert_eq!(Err(Utf8Error::Invalid), builder.append(Utf8Builder::from("ab")));
+
+    assert_eq!("中文", builder.as_valid_str());
+
+    let builders = vec![
+        Utf8Builder::from("中"),
+        Utf8Builder::new(),
+        Utf8Builder::try_from_partial(&bytes[3..5]).unwrap(),
+    ];
+
+    let merged: Utf8Builder = builders.into_iter().collect();
+
+    assert_eq!(&bytes[..5], merged.into_bytes().0.as_slice());
+
+    let merged: Utf8Builder =
+        vec![Utf8Builder::from("ab"), Utf8Builder::from("中"), Utf8Builder::from("c")]
+            .into_iter()
+            .sum();
+
+    assert_eq!("ab中c", merged.finalize().unwrap());
+
+    let mut builder = Utf8Builder::try_from_partial(&bytes[..2]).unwrap();
+
+    builder.extend(vec![Utf8Builder::from("a"), Utf8Builder::from("b")]);
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.finalize());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_limit(Some(3));
+    builder.extend(vec![Utf8Builder::from("ab"), Utf8Builder::from("cd")]);
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), builder.finalize());
+}
+
+#[test]
+fn merge_sorted_lines() {
+    let a = Utf8Builder::from("apple\r\ncherry\n中文");
+    let b = Utf8Builder::from("banana\ncherry\ndate\n");
+
+    let merged = a.merge_sorted_lines(&b).unwrap();
+
+    assert_eq!("apple\nbanana\ncherry\ncherry\ndate\n中文\n", merged.finalize().unwrap());
+    assert_eq!(
+        "",
+        Utf8Builder::new().merge_sorted_lines(&Utf8Builder::new()).unwrap().finalize().unwrap()
+    );
+
+    let partial = Utf8Builder::try_from_partial(b"z\n\xE4").unwrap();
+
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 1, need: 2
+        }),
+        a.merge_sorted_lines(&partial).map(|_| ())
+    );
+}
+
+#[test]
+fn push_str_lossy_from_bytes() {
+    let bytes: &[u8] = b"a\xE4\xB8\xADb\xFFc\xE4\xB8d\xF0\x9F\x98";
+
+    for chunk_size in 1..=bytes.len() {
+        let mut builder = Utf8Builder::new();
+
+        for chunk in bytes.chunks(chunk_size) {
+            builder.push_str_lossy_from_bytes(chunk).unwrap();
+        }
+
+        assert_eq!("a中b\u{FFFD}c\u{FFFD}d", builder.as_valid_str());
+
+        builder.push_str_lossy_from_bytes(b"\x80").unwrap();
+
+        assert_eq!("a中b\u{FFFD}c\u{FFFD}d😀", builder.finalize().unwrap());
+    }
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_limit(Some(4));
+    builder.push_str_lossy_from_bytes(b"a\xE4").unwrap();
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), builder.push_str_lossy_from_bytes(b"\xFF"));
+
+    builder.push_str_lossy_from_bytes(b"\xB8\xAD").unwrap();
+
+    assert_eq!("a中", builder.finalize().unwrap());
+}
+
+#[test]
+fn push_str_lossy_from_bytes_limit() {
+    // nothing is allocated past the limit before it is checked
+    for bytes in [vec![b'a'; 1 << 20], vec![0xFF; 1 << 20]] {
+        let mut builder = Utf8Builder::new();
+
+        builder.set_limit(Some(10));
+
+        assert_eq!(Err(Utf8Error::LimitExceeded), builder.push_str_lossy_from_bytes(&bytes));
+        assert!(builder.capacity() < 64);
+        assert!(builder.is_empty());
+    }
+}
+
+#[test]
+fn into_lossy_string() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_str("a中").unwrap();
+
+    assert_eq!("a中", builder.clone().into_lossy_string());
+
+    builder.push_chunk(b"\xE6\x96").unwrap();
+
+    assert_eq!("a中\u{FFFD}", builder.clone().into_lossy_string());
+
+    builder.push_chunk(b"\x87b\xFFc").unwrap_err();
+
+    assert_eq!("a中文b\u{FFFD}", builder.into_lossy_string());
+}
+
+#[test]
+fn push_shared() {
+    let s = Arc::new(String::from("中文"));
+
+    let ptr = s.as_ptr();
+
+    let mut builder = Utf8Builder::new();
+
+    builder.push_shared(s).unwrap();
+
+    assert_eq!(ptr, builder.as_valid_str().as_ptr());
+
+    builder.push_shared(Rc::new(String::from("ab"))).unwrap();
+
+    let shared: Arc<str> = Arc::from("cd");
+
+    builder.push_shared(shared.clone()).unwrap();
+
+    let s = builder.finalize().unwrap();
+
+    assert_eq!("中文abcd", s);
+
+    let s = Arc::new(String::from("中文"));
+
+    let mut builder = Utf8Builder::new();
+
+    builder.push_shared(s.clone()).unwrap();
+
+    assert_eq!("中文", builder.finalize().unwrap());
+    assert_eq!(1, Arc::strong_count(&s));
+}
+
+#[test]
+fn push_outcome() {
+    let bytes = "a😀".as_bytes();
+
+    let mut builder = Utf8Builder::new();
+
+    assert_eq!(PushOutcome::Complete, builder.push(bytes[0]).unwrap());
+    assert_eq!(PushOutcome::NeedMore(3), builder.push(bytes[1]).unwrap());
+    assert_eq!(PushOutcome::NeedMore(1), builder.push_chunk(&bytes[2..4]).unwrap());
+    assert_eq!(PushOutcome::Complete, builder.push_chunk(&bytes[4..]).unwrap());
+    assert_eq!(PushOutcome::Complete, builder.push_chunk(b"").unwrap());
+}
+
+#[test]
+fn finalize_lines() {
+    let builder = Utf8Builder::from("a\r\n中\n\nb");
+
+    assert_eq!(vec!["a", "中", "", "b"], builder.clone().finalize_lines(false).unwrap());
+    assert_eq!(vec!["a\r\n", "中\n", "\n", "b"], builder.finalize_lines(true).unwrap());
+
+    let builder = Utf8Builder::from("a\n");
+
+    assert_eq!(vec!["a"], builder.clone().finalize_lines(false).unwrap());
+    assert_eq!(vec!["a\n"], builder.finalize_lines(true).unwrap());
+
+    let builder = Utf8Builder::try_from_partial(b"a\n\xE4").unwrap();
+
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 1, need: 2
+        }),
+        builder.finalize_lines(false)
+    );
+}
+
+#[test]
+fn ensure_ends_with() {
+    let mut builder = Utf8Builder::from("中文");
+
+    builder.ensure_ends_with("\n").unwrap();
+    builder.ensure_ends_with("\n").unwrap();
+    builder.ensure_ends_with("文\n").unwrap();
+    builder.ensure_ends_with("").unwrap();
+
+    assert_eq!("中文\n", builder.as_valid_str());
+
+    builder.push_chunk(&"字".as_bytes()[..2]).unwrap();
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.ensure_ends_with("\n"));
+}
+
+#[test]
+fn finalize_parse() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(b"12").unwrap();
+    builder.push_chunk(b"34").unwrap();
+
+    assert_eq!(Ok(1234), builder.clone().finalize_parse::<u32>());
+    assert!(matches!(builder.finalize_parse::<u8>(), Err(ParseError::Parse(_))));
+
+    let builder = Utf8Builder::try_from_partial(b"1\xE4").unwrap();
+
+    assert!(matches!(
+        builder.finalize_parse::<u32>(),
+        Err(ParseError::Utf8(Utf8Error::Incomplete { .. }))
+    ));
+}
+
+#[test]
+fn shrink_pending_into() {
+    let bytes = "a😀".as_bytes();
+
+    let mut builder = Utf8Builder::try_from_partial(&bytes[..3]).unwrap();
+
+    let mut tail = [0; 4];
+
+    assert_eq!(2, builder.shrink_pending_into(&mut tail));
+    assert!(builder.is_valid());
+    assert_eq!("a", builder.finalize().unwrap());
+
+    let mut builder = Utf8Builder::try_from_partial(&tail[..2]).unwrap();
+
+    builder.push_chunk(&bytes[3..]).unwrap();
+
+    assert_eq!(0, builder.shrink_pending_into(&mut tail));
+    assert_eq!("😀", builder.finalize().unwrap());
+}
+
+#[test]
+fn error_conversion() {
+    #[derive(Debug, PartialEq)]
+    enum MyError {
+        Utf8(Utf8Error),
+    }
+
+    impl From<Utf8Error> for MyError {
+        fn from(error: Utf8Error) -> Self {
+            MyError::Utf8(error)
+        }
+    }
+
+    fn build(bytes: &[u8]) -> Result<String, MyError> {
+        let mut builder = Utf8Builder::new();
+
+        builder.push_with::<MyError>(b'a')?;
+        builder.push_char_with::<MyError>('中')?;
+        builder.push_str_with::<MyError>("b")?;
+        builder.push_chunk_with::<MyError>(bytes)?;
+
+        builder.finalize_with()
+    }
+
+    assert_eq!(Ok(String::from("a中bc")), build(b"c"));
+    assert_eq!(Err(MyError::Utf8(Utf8Error::Invalid)), build(b"\xFF"));
+    assert_eq!(
+        Err(MyError::Utf8(Utf8Error::Incomplete {
+            have: 1, need: 2
+        })),
+        build(b"\xE4")
+    );
+}
+
+#[test]
+fn push_chunk_saturating() {
+    let mut builder = Utf8Builder::new();
+
+    builder.set_limit(Some(8));
+
+    assert_eq!(1, builder.push_chunk_saturating(b"a").unwrap());
+    assert_eq!(6, builder.push_chunk_saturating("中文字".as_bytes()).unwrap());
+    assert_eq!(1, builder.push_chunk_saturating(b"bcd").unwrap());
+    assert_eq!(0, builder.push_chunk_saturating(b"e").unwrap());
+    assert_eq!("a中文b", builder.finalize().unwrap());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_limit(Some(4));
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk_saturating(b"a\xFFbcdef"));
+
+    let mut builder = Utf8Builder::new();
+
+    assert_eq!(3, builder.push_chunk_saturating("中".as_bytes()).unwrap());
+}
+
+#[test]
+fn push_exact() {
+    let data = "ab中文".as_bytes();
+
+    let mut builder = Utf8Builder::new();
+    let mut remaining = 5;
+
+    assert_eq!(4, builder.push_exact(&data[..4], &mut remaining).unwrap());
+    assert_eq!(1, remaining);
+    assert_eq!(1, builder.push_exact(&data[4..], &mut remaining).unwrap());
+    assert_eq!(0, remaining);
+    assert_eq!(0, builder.push_exact(&data[5..], &mut remaining).unwrap());
+    assert_eq!("ab中", builder.finalize().unwrap());
+
+    let mut builder = Utf8Builder::new();
+    let mut remaining = 4;
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_exact(data, &mut remaining));
+    assert_eq!(2, builder.error().unwrap().offset);
+}
+
+#[test]
+fn finalize_extend() {
+    let mut message = vec![0x81, 0x06];
+
+    Utf8Builder::from("ab中").finalize_extend(&mut message).unwrap();
+
+    assert_eq!(b"\x81\x06ab\xE4\xB8\xAD", message.as_slice());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(b"c\xE4").unwrap();
+
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 1, need: 2
+        }),
+        builder.finalize_extend(&mut message)
+    );
+    assert_eq!(7, message.len());
+}
+
+#[test]
+fn longest_common_valid_prefix() {
+    let mut builder = Utf8Builder::new();
+
+    assert_eq!("", builder.longest_common_valid_prefix("ab中文"));
+
+    builder.push_chunk("ab中".as_bytes()).unwrap();
+
+    assert_eq!("ab中", builder.longest_common_valid_prefix("ab中文"));
+    assert_eq!("ab", builder.longest_common_valid_prefix("ab丫"));
+    assert_eq!("a", builder.longest_common_valid_prefix("a"));
+
+    builder.push_chunk(&"文".as_bytes()[..2]).unwrap();
+
+    assert_eq!("ab中", builder.longest_common_valid_prefix("ab中文"));
+}
+
+#[test]
+fn reserve_for_chars() {
+    let mut builder = Utf8Builder::from("ab");
+
+    builder.reserve_for_chars(10, None);
+
+    assert!(builder.capacity() >= 42);
+
+    let mut builder = Utf8Builder::new();
+
+    builder.reserve_for_chars(10, Some(3));
+
+    assert!(builder.capacity() >= 30);
+
+    let mut builder = Utf8Builder::new();
+
+    builder.reserve_for_chars(10, Some(0));
+
+    assert!(builder.capacity() >= 10);
+}
+
+#[test]
+fn try_extend_from_iter() {
+    let mut builder = Utf8Builder::new();
+
+    let mut iter = b"a\xE4\xB8\xAD\xFFb".iter().copied();
+
+    assert_eq!(4, builder.try_extend_from_iter(iter.by_ref().take(4)).unwrap());
+    assert_eq!(
+        Err(ExtendError {
+            accepted: 0, kind: Utf8Error::Invalid
+        }),
+        builder.try_extend_from_iter(iter.by_ref())
+    );
+    assert_eq!(Some(b'b'), iter.next());
+    assert_eq!("a中", builder.as_valid_str());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_limit(Some(5));
+
+    assert_eq!(
+        Err(ExtendError {
+            accepted: 2, kind: Utf8Error::LimitExceeded
+        }),
+        builder.try_extend("a中文".chars())
+    );
+    assert_eq!(1, builder.try_extend(Some('!')).unwrap());
+    assert_eq!("a中!", builder.finalize().unwrap());
+
+    let mut builder = Utf8Builder::new();
+
+    let mut iter = b"ab\xE4\xB8c\xAD".iter().copied();
+
+    let err = builder.try_extend_from_iter(iter.by_ref()).unwrap_err();
+
+    assert_eq!(4, err.accepted);
+    assert_eq!(Utf8Error::Invalid, Utf8Error::from(err));
+    assert_eq!(Some(0xAD), iter.next());
+}
+
+#[test]
+fn ends_pending_with() {
+    let mut buf = [0; 4];
+
+    let mut builder = Utf8Builder::new();
+
+    builder.push_str("中").unwrap();
+
+    assert!(!builder.ends_pending_with('中'.encode_utf8(&mut buf).as_bytes()));
+
+    builder.push_chunk(&"文".as_bytes()[..2]).unwrap();
+
+    assert!(builder.ends_pending_with('文'.encode_utf8(&mut buf).as_bytes()));
+    assert!(builder.ends_pending_with(&"文".as_bytes()[..2]));
+    assert!(!builder.ends_pending_with(&"文".as_bytes()[..1]));
+    assert!(!builder.ends_pending_with('中'.encode_utf8(&mut buf).as_bytes()));
+}
+
+#[test]
+fn push_chunk_diverted() {
+    let bytes: &[u8] = b"a\xE4\xB8\xADb\xFF\xFEc\xE4\xB8d\xF0\x9F\x98";
+
+    for chunk_size in 1..=bytes.len() {
+        let mut builder = Utf8Builder::new();
+        let mut diverted = Diverted::new();
+
+        for chunk in bytes.chunks(chunk_size) {
+            builder.push_chunk_diverted(chunk, &mut diverted).unwrap();
+        }
+
+        builder.push_chunk_diverted(b"\x80", &mut diverted).unwrap();
+
+        assert_eq!(b"\xFF\xFE\xE4\xB8", diverted.bytes.as_slice());
+        assert_eq!([(5, 0..2), (6, 2..4)], diverted.spans.as_slice());
+        assert_eq!("a中bcd😀", builder.finalize().unwrap());
+    }
+
+    let mut builder = Utf8Builder::new();
+    let mut diverted = Diverted::new();
+
+    builder.set_limit(Some(2));
+
+    assert_eq!(
+        Err(Utf8Error::LimitExceeded),
+        builder.push_chunk_diverted(b"\xFFabc", &mut diverted)
+    );
+    assert_eq!(Diverted::new(), diverted);
+}
+
+#[test]
+fn push_chunk_at_most() {
+    let data = "ab中文".as_bytes();
+
+    let mut builder = Utf8Builder::new();
+
+    assert_eq!(2, builder.push_chunk_at_most(data, 4).unwrap());
+    assert_eq!(3, builder.push_chunk_at_most(&data[2..], 5).unwrap());
+    assert_eq!(0, builder.push_chunk_at_most(&data[5..], 2).unwrap());
+    assert_eq!(3, builder.push_chunk_at_most(&data[5..], 3).unwrap());
+    assert_eq!("ab中文", builder.as_valid_str());
+
+    builder.set_limit(Some(11));
+
+    assert_eq!(2, builder.push_chunk_at_most(b"cdef", 2).unwrap());
+    assert_eq!(1, builder.push_chunk_at_most(b"ghi", 3).unwrap());
+
+    builder.set_limit(None);
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_chunk_at_most(b"\xFFg", 3));
+}
+
+#[test]
+fn push_repeat_char() {
+    let mut builder = Utf8Builder::from("a");
+
+    builder.push_repeat_char(' ', 7).unwrap();
+    builder.push_repeat_char('中', 3).unwrap();
+    builder.push_repeat_char('b', 0).unwrap();
+
+    assert_eq!("a       中中中", builder.as_valid_str());
+
+    builder.set_limit(Some(21));
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), builder.push_repeat_char('😀', 2));
+    assert_eq!(Err(Utf8Error::LimitExceeded), builder.push_repeat_char('😀', usize::MAX));
+
+    builder.push_repeat_char('😀', 1).unwrap();
+
+    assert_eq!("a       中中中😀", builder.finalize().unwrap());
+}
+
+#[test]
+fn carry_from() {
+    let mut old = Utf8Builder::new();
+
+    old.push_chunk(&"ab中".as_bytes()[..4]).unwrap();
+
+    let mut new = Utf8Builder::new();
+
+    new.set_limit(Some(1));
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), new.carry_from(&mut old));
+    assert_eq!(4, old.len());
+
+    new.set_limit(None);
+
+    assert_eq!(2, new.carry_from(&mut old).unwrap());
+    assert_eq!(0, new.carry_from(&mut old).unwrap());
+
+    new.push_chunk(&"中".as_bytes()[2..]).unwrap();
+
+    assert_eq!("ab", old.finalize().unwrap());
+    assert_eq!("中", new.finalize().unwrap());
+}
@@ -0,0 +1,50 @@
+use utf8_builder::{CaseFold, CollapseWhitespace, NormalizeNewlines, StripAnsi, TransformPipeline};
+
+#[test]
+fn transform_pipeline() {
+    let input = "\x1B[1;31mHELLO\x1B[0m, Wörld!\r\n\x1B]0;title\x07LINE 2\rΣ\n";
+
+    for chunk_size in 1..=8 {
+        let mut pipeline = TransformPipeline::new()
+            .stage(StripAnsi::new())
+            .stage(NormalizeNewlines::new())
+            .stage(CaseFold);
+
+        for chunk in input.as_bytes().chunks(chunk_size) {
+            pipeline.push_chunk(chunk).unwrap();
+        }
+
+        assert_eq!("hello, wörld!\nline 2\nσ\n", pipeline.finalize().unwrap());
+    }
+
+    let mut pipeline = TransformPipeline::new();
+
+    pipeline.push_chunk(b"a\xE4").unwrap();
+
+    assert!(pipeline.finalize().is_err());
+}
+
+#[test]
+fn transform_pipeline_lossy() {
+    let mut pipeline = TransformPipeline::new_lossy().stage(CaseFold);
+
+    pipeline.push_chunk(b"A\xFFB\xE4").unwrap();
+
+    assert_eq!("a\u{FFFD}b", pipeline.as_str());
+    assert_eq!("a\u{FFFD}b\u{FFFD}", pipeline.finalize().unwrap());
+}
+
+#[test]
+fn collapse_whitespace() {
+    let input = "  a \t\r\n b\u{3000}\u{3000}中\n";
+
+    for chunk_size in 1..=4 {
+        let mut pipeline = TransformPipeline::new().stage(CollapseWhitespace::new());
+
+        for chunk in input.as_bytes().chunks(chunk_size) {
+            pipeline.push_chunk(chunk).unwrap();
+        }
+
+        assert_eq!(" a b 中 ", pipeline.finalize().unwrap());
+    }
+}
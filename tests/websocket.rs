@@ -0,0 +1,40 @@
+use utf8_builder::{WebSocketTextError, WebSocketTextValidator};
+
+#[test]
+fn websocket_text_validator() {
+    let text = "Hello, 世界! 😀";
+    let bytes = text.as_bytes();
+
+    let mut validator = WebSocketTextValidator::new();
+
+    assert_eq!(None, validator.push_text_frame(&bytes[..8], false).unwrap());
+    assert!(validator.is_in_progress());
+    assert_eq!(None, validator.push_continuation_frame(&bytes[8..16], false).unwrap());
+    assert_eq!(
+        Some(text.to_string()),
+        validator.push_continuation_frame(&bytes[16..], true).unwrap()
+    );
+    assert!(!validator.is_in_progress());
+
+    assert_eq!(
+        WebSocketTextError::UnexpectedContinuation,
+        validator.push_continuation_frame(b"a", true).unwrap_err()
+    );
+
+    validator.push_text_frame(b"a", false).unwrap();
+
+    assert_eq!(
+        WebSocketTextError::UnfinishedMessage,
+        validator.push_text_frame(b"b", true).unwrap_err()
+    );
+
+    let err = validator.push_text_frame(&bytes[..8], true).unwrap_err();
+
+    assert_eq!(WebSocketTextError::InvalidUtf8, err);
+    assert_eq!(1007, err.close_code());
+
+    let err = validator.push_text_frame(b"\xFF", false).unwrap_err();
+
+    assert_eq!(WebSocketTextError::InvalidUtf8, err);
+    assert!(!validator.is_in_progress());
+}
@@ -0,0 +1,46 @@
+use utf8_builder::{Utf8Error, WideBuilder};
+
+#[test]
+fn push_wide() {
+    let text = "a中😀b😃";
+
+    let units: Vec<u16> = text.encode_utf16().collect();
+
+    for chunk_size in 1..=units.len() {
+        let mut builder = WideBuilder::new();
+
+        for chunk in units.chunks(chunk_size) {
+            builder.push_wide(chunk).unwrap();
+        }
+
+        assert_eq!(text, builder.finalize().unwrap());
+    }
+
+    let long: Vec<u16> = "中".repeat(200).encode_utf16().collect();
+
+    let mut builder = WideBuilder::new();
+
+    builder.push_wide(&long).unwrap();
+
+    assert_eq!("中".repeat(200), builder.finalize().unwrap());
+
+    let mut builder = WideBuilder::new();
+
+    builder.push_wide(&[0x61, 0xD83D]).unwrap();
+
+    assert!(!builder.is_valid());
+    assert_eq!(
+        Err(Utf8Error::Incomplete {
+            have: 2, need: 2
+        }),
+        builder.clone().finalize()
+    );
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_wide(&[0x62]));
+    assert_eq!("a", builder.builder().as_valid_str());
+
+    let mut builder = WideBuilder::new();
+
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_wide(&[0x61, 0xDE00, 0x62]));
+    assert_eq!(Err(Utf8Error::Invalid), builder.push_wide(&[0x63]));
+    assert_eq!("a", builder.builder().as_valid_str());
+}
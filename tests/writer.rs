@@ -0,0 +1,57 @@
+#![cfg(feature = "std")]
+
+use std::io::{self, Write};
+
+use utf8_builder::{FinalizeError, Utf8Builder, Utf8Error};
+
+struct Recorder(Vec<Vec<u8>>);
+
+impl Write for Recorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.push(buf.to_vec());
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn finalize_to_writer() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_str("中文ab").unwrap();
+
+    let mut v = Vec::new();
+
+    assert_eq!(8, builder.finalize_to_writer(&mut v).unwrap());
+    assert_eq!("中文ab".as_bytes(), v.as_slice());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.push_chunk(&"中".as_bytes()[..2]).unwrap();
+
+    let mut v = Vec::new();
+
+    assert!(matches!(
+        builder.finalize_to_writer(&mut v),
+        Err(FinalizeError::Utf8(Utf8Error::Incomplete {
+            have: 2, need: 1
+        }))
+    ));
+    assert!(v.is_empty());
+}
+
+#[test]
+fn finalize_to_writer_chunked() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_str("abcdefg").unwrap();
+
+    let mut recorder = Recorder(Vec::new());
+
+    assert_eq!(7, builder.finalize_to_writer_chunked(&mut recorder, 3).unwrap());
+    assert_eq!(vec![b"abc".to_vec(), b"def".to_vec(), b"g".to_vec()], recorder.0);
+}
@@ -0,0 +1,35 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{executor::block_on, task::noop_waker};
+use utf8_builder::{Utf8Builder, Utf8Error};
+
+#[test]
+fn push_chunk_yielding() {
+    let mut builder = Utf8Builder::new();
+
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+
+    let mut push = builder.push_chunk_yielding("ab中文".as_bytes(), 3);
+    let mut push = Pin::new(&mut push);
+
+    assert_eq!(Poll::Pending, push.as_mut().poll(&mut cx));
+    assert_eq!(Poll::Pending, push.as_mut().poll(&mut cx));
+    assert_eq!(Poll::Ready(Ok(())), push.as_mut().poll(&mut cx));
+
+    block_on(builder.push_chunk_yielding(b"", 3)).unwrap();
+
+    assert_eq!(Err(Utf8Error::Invalid), block_on(builder.push_chunk_yielding(b"cd\xFFe", 2)));
+    assert_eq!("ab中文cd", builder.as_valid_str());
+
+    let mut builder = Utf8Builder::new();
+
+    builder.set_limit(Some(4));
+
+    assert_eq!(Err(Utf8Error::LimitExceeded), block_on(builder.push_chunk_yielding(b"abcde", 2)));
+    assert!(builder.is_empty());
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "zeroize")]
+
+use utf8_builder::Utf8Builder;
+use zeroize::Zeroize;
+
+#[test]
+fn zeroize() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_str("password: 密碼").unwrap();
+    builder.push_chunk(&"碼".as_bytes()[..1]).unwrap();
+    builder.zeroize();
+
+    assert!(builder.is_empty());
+    assert!(builder.is_valid());
+
+    let mut builder = Utf8Builder::with_capacity(1);
+
+    for _ in 0..100 {
+        builder.push_str("secret").unwrap();
+    }
+
+    assert_eq!("secret".repeat(100), *builder.finalize_zeroizing().unwrap());
+}
+
+#[test]
+fn zeroize_shared() {
+    let mut builder = Utf8Builder::new();
+
+    builder.push_str("secret").unwrap();
+
+    let snapshot = builder.freeze();
+
+    drop(builder);
+
+    assert_eq!("secret", &*snapshot);
+
+    let mut builder = Utf8Builder::with_capacity(64);
+
+    builder.push_str("secret").unwrap();
+
+    assert_eq!("secret", builder.leak().unwrap());
+}